@@ -29,7 +29,7 @@ fn random_text(scene: &mut Scene, text: &str) -> TextId {
     scene
         .text()
         .with_text(text)
-        .with_font_size(20)
+        .with_font_size(0.25)
         .with_color(color)
         .with_position(x, y)
         .make()
@@ -1,4 +1,5 @@
-use crate::{Color, ColorExtension};
+use crate::{Color, ColorExtension, TextColors};
+use std::ops::Range;
 
 use super::*;
 
@@ -68,3 +69,14 @@ pub trait WithColor: WithId {
         }
     }
 }
+
+pub trait WithTextColor: WithId {
+    /// Animate the color of the characters in the byte `range` of a text,
+    /// leaving the rest of it unchanged.
+    fn set_range_color(&self, range: Range<usize>, color: Color) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: Animation::by(TextColors::range(range, color)).into(),
+        }
+    }
+}
@@ -7,8 +7,9 @@ use bevy_ecs::{
 
 // use crate::prelude::*;
 use crate::{
-    prelude::Direction, Angle, Bounds, EaseType, FillColor, FontSize, Interpolate, Opacity, Path,
-    PathCompletion, Position, Scale, Scene, Size, StrokeColor, StrokeWeight, Value, Vector,
+    prelude::Direction, Angle, Bounds, Color, EaseType, FillColor, FontSize, InnerRadius,
+    Interpolate, Opacity, Path, PathCompletion, PointMap, Position, Scale, Scene, Size, StartAngle,
+    StrokeColor, StrokeWeight, SweepAngle, TextColors, Transform, Value, Vector,
 };
 
 mod builder;
@@ -33,6 +34,12 @@ pub trait WithId {
 #[derive(Component)]
 pub struct Animations<C: Interpolate + Component>(pub Vec<Animation<C>>);
 
+/// Pointwise deformations of a [Path] that have not started yet. Each one
+/// becomes a regular [Animation] of the [Path] once its start time is
+/// reached, since the deformed path depends on the path at that time.
+#[derive(Component)]
+pub struct PathMaps(pub Vec<Animation<PointMap>>);
+
 /// Basic structure to describe an animation.
 #[derive(Component, Debug, Clone)]
 pub struct Animation<T> {
@@ -270,6 +277,76 @@ impl Animation<Position> {
     }
 }
 
+/// Length of the line segments making up paths deformed by non-affine maps,
+/// in scene units.
+const RESAMPLE_LENGTH: f32 = 0.1;
+
+impl Animation<PointMap> {
+    /// Animation moving every point of a path to its image by `f`, in scene
+    /// coordinates.
+    pub fn map(f: PointMap) -> Self {
+        Self {
+            begin: None,
+            end: Value::Absolute(f),
+            duration: 1.0,
+            start_time: 0.0,
+            rate_func: Default::default(),
+            init_duration: true,
+            init_start_time: true,
+            init_rate_func: true,
+        }
+    }
+
+    /// Animation of `path` to its image by the map, with the same timing.
+    /// `to_scene` is the transform from the object's local coordinates to
+    /// scene coordinates, in which the map is given.
+    pub(crate) fn resolve(&self, path: &Path, to_scene: &Transform) -> Animation<Path> {
+        let (begin, end) = match (&self.end, to_scene.inverse()) {
+            (Value::Absolute(f), Some(to_local)) => {
+                let begin = if f.affine {
+                    path.clone()
+                } else {
+                    path.resampled(RESAMPLE_LENGTH)
+                };
+                let end = begin
+                    .map_points(|p| to_local.transform_point(f.apply(to_scene.transform_point(p))));
+                (begin, end)
+            }
+            _ => (path.clone(), path.clone()),
+        };
+        Animation {
+            begin: Some(begin),
+            end: Value::Absolute(end),
+            duration: self.duration,
+            start_time: self.start_time,
+            rate_func: self.rate_func,
+            init_duration: false,
+            init_start_time: false,
+            init_rate_func: false,
+        }
+    }
+}
+
+impl Animation<TextColors> {
+    /// Update function for [TextColors] to be called by [System](bevy_ecs::prelude::System).
+    ///
+    /// Relative changes recolor part of the text. Characters without a color
+    /// of their own start from `base`, the color of the whole text.
+    pub fn update_text_colors(&mut self, colors: &mut TextColors, progress: f32, base: Color) {
+        match (&mut self.begin, &mut self.end) {
+            (Some(begin), Value::Absolute(to)) => *colors = begin.interp(to, progress),
+            (None, Value::Absolute(_to)) => {
+                self.begin = Some(colors.clone());
+            }
+            (None, Value::Relative(by)) => {
+                self.begin = Some(colors.filled(by, base));
+                self.end = Value::Absolute(colors.clone() + by.clone());
+            }
+            _ => (),
+        }
+    }
+}
+
 impl<T> Into<Vec<AnimationType>> for Animation<T>
 where
     Animation<T>: Into<AnimationType>,
@@ -289,9 +366,14 @@ pub enum AnimationType {
     Size(Animation<Size>),
     Scale(Animation<Scale>),
     FontSize(Animation<FontSize>),
+    StartAngle(Animation<StartAngle>),
+    SweepAngle(Animation<SweepAngle>),
+    InnerRadius(Animation<InnerRadius>),
     Opacity(Animation<Opacity>),
     PathCompletion(Animation<PathCompletion>),
     Path(Animation<Path>),
+    PathMap(Animation<PointMap>),
+    TextColors(Animation<TextColors>),
 }
 
 impl Into<AnimationType> for Animation<StrokeColor> {
@@ -342,6 +424,24 @@ impl Into<AnimationType> for Animation<FontSize> {
     }
 }
 
+impl Into<AnimationType> for Animation<StartAngle> {
+    fn into(self) -> AnimationType {
+        AnimationType::StartAngle(self)
+    }
+}
+
+impl Into<AnimationType> for Animation<SweepAngle> {
+    fn into(self) -> AnimationType {
+        AnimationType::SweepAngle(self)
+    }
+}
+
+impl Into<AnimationType> for Animation<InnerRadius> {
+    fn into(self) -> AnimationType {
+        AnimationType::InnerRadius(self)
+    }
+}
+
 impl Into<AnimationType> for Animation<Opacity> {
     fn into(self) -> AnimationType {
         AnimationType::Opacity(self)
@@ -360,6 +460,18 @@ impl Into<AnimationType> for Animation<Path> {
     }
 }
 
+impl Into<AnimationType> for Animation<PointMap> {
+    fn into(self) -> AnimationType {
+        AnimationType::PathMap(self)
+    }
+}
+
+impl Into<AnimationType> for Animation<TextColors> {
+    fn into(self) -> AnimationType {
+        AnimationType::TextColors(self)
+    }
+}
+
 fn insert_animation<C: Component + Interpolate>(
     animation: Animation<C>,
     world: &mut World,
@@ -372,7 +484,7 @@ fn insert_animation<C: Component + Interpolate>(
     }
 }
 
-fn set_properties<T: Component + Interpolate>(
+fn set_properties<T>(
     animation: &mut Animation<T>,
     start_time: f32,
     duration: f32,
@@ -423,6 +535,15 @@ impl EntityAnimations {
                 AnimationType::FontSize(animation) => {
                     insert_animation(animation, world, self.entity);
                 }
+                AnimationType::StartAngle(animation) => {
+                    insert_animation(animation, world, self.entity);
+                }
+                AnimationType::SweepAngle(animation) => {
+                    insert_animation(animation, world, self.entity);
+                }
+                AnimationType::InnerRadius(animation) => {
+                    insert_animation(animation, world, self.entity);
+                }
                 AnimationType::Opacity(animation) => {
                     insert_animation(animation, world, self.entity);
                 }
@@ -432,6 +553,25 @@ impl EntityAnimations {
                 AnimationType::Path(animation) => {
                     insert_animation(animation, world, self.entity);
                 }
+                AnimationType::PathMap(animation) => {
+                    // Path animations are run by the same system, which
+                    // expects the object to have them.
+                    if world.get::<Animations<Path>>(self.entity).is_none() {
+                        world
+                            .entity_mut(self.entity)
+                            .insert(Animations::<Path>(Vec::new()));
+                    }
+                    if let Some(mut maps) = world.get_mut::<PathMaps>(self.entity) {
+                        maps.0.push(animation);
+                    } else {
+                        world
+                            .entity_mut(self.entity)
+                            .insert(PathMaps(vec![animation]));
+                    }
+                }
+                AnimationType::TextColors(animation) => {
+                    insert_animation(animation, world, self.entity);
+                }
             };
         }
     }
@@ -445,9 +585,14 @@ impl EntityAnimations {
             AnimationType::Scale(animation) => animation.start_time,
             AnimationType::Size(animation) => animation.start_time,
             AnimationType::FontSize(animation) => animation.start_time,
+            AnimationType::StartAngle(animation) => animation.start_time,
+            AnimationType::SweepAngle(animation) => animation.start_time,
+            AnimationType::InnerRadius(animation) => animation.start_time,
             AnimationType::Opacity(animation) => animation.start_time,
             AnimationType::PathCompletion(animation) => animation.start_time,
             AnimationType::Path(animation) => animation.start_time,
+            AnimationType::PathMap(animation) => animation.start_time,
+            AnimationType::TextColors(animation) => animation.start_time,
         }
     }
     pub fn set_properties(&mut self, start_time: f32, duration: f32, rate_func: EaseType) {
@@ -477,6 +622,15 @@ impl EntityAnimations {
                 AnimationType::FontSize(ref mut animation) => {
                     set_properties(animation, start_time, duration, rate_func);
                 }
+                AnimationType::StartAngle(ref mut animation) => {
+                    set_properties(animation, start_time, duration, rate_func);
+                }
+                AnimationType::SweepAngle(ref mut animation) => {
+                    set_properties(animation, start_time, duration, rate_func);
+                }
+                AnimationType::InnerRadius(ref mut animation) => {
+                    set_properties(animation, start_time, duration, rate_func);
+                }
                 AnimationType::Opacity(ref mut animation) => {
                     set_properties(animation, start_time, duration, rate_func);
                 }
@@ -486,6 +640,12 @@ impl EntityAnimations {
                 AnimationType::Path(ref mut animation) => {
                     set_properties(animation, start_time, duration, rate_func);
                 }
+                AnimationType::PathMap(ref mut animation) => {
+                    set_properties(animation, start_time, duration, rate_func);
+                }
+                AnimationType::TextColors(ref mut animation) => {
+                    set_properties(animation, start_time, duration, rate_func);
+                }
             }
         }
     }
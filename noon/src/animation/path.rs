@@ -1,10 +1,53 @@
 use super::*;
-use crate::Path;
+use crate::{Complex, Path, Point};
 
 pub trait WithPath: WithId {
     fn show_creation(&self) -> EntityAnimations {
+        EntityAnimations::show_creation(self.id())
+    }
+    fn fade_in(&self) -> EntityAnimations {
+        EntityAnimations::fade_in(self.id())
+    }
+    fn fade_out(&self) -> EntityAnimations {
+        EntityAnimations::fade_out(self.id())
+    }
+    /// Move every point of the object by the linear map `[[a, b], [c, d]]`
+    /// of scene coordinates, which sends `(x, y)` to `(a x + b y, c x + d y)`.
+    fn apply_matrix(&self, matrix: [[f32; 2]; 2]) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: Animation::map(PointMap::affine(Transform::from_matrix(matrix))).into(),
+        }
+    }
+    /// Move every point `p` of the object to `f(p)`, in scene coordinates.
+    fn apply_function(
+        &self,
+        f: impl Fn(Point) -> Point + Send + Sync + 'static,
+    ) -> EntityAnimations {
         EntityAnimations {
             entity: self.id(),
+            animations: Animation::map(PointMap::new(f)).into(),
+        }
+    }
+    /// Move every point of the object, seen as a complex number `z`, to `f(z)`.
+    fn apply_complex_function(
+        &self,
+        f: impl Fn(Complex) -> Complex + Send + Sync + 'static,
+    ) -> EntityAnimations {
+        self.apply_function(move |p| f(p.into()).into())
+    }
+    fn morph(&self, entity: impl Into<Entity>) -> EntityAnimations {
+        EntityAnimations::morph(self.id(), entity)
+    }
+}
+
+/// Animations of any object drawn from a path, for objects known only by
+/// their [Entity], e.g. the parts of composite objects of different kinds.
+impl EntityAnimations {
+    /// Same as [WithPath::show_creation].
+    pub fn show_creation(entity: impl Into<Entity>) -> Self {
+        EntityAnimations {
+            entity: entity.into(),
             animations: vec![
                 Animation::<Opacity>::to(Opacity::FULL)
                     .with_rate_func(EaseType::Instance)
@@ -13,9 +56,10 @@ pub trait WithPath: WithId {
             ],
         }
     }
-    fn fade_in(&self) -> EntityAnimations {
+    /// Same as [WithPath::fade_in].
+    pub fn fade_in(entity: impl Into<Entity>) -> Self {
         EntityAnimations {
-            entity: self.id(),
+            entity: entity.into(),
             animations: vec![
                 Animation::<PathCompletion>::to(PathCompletion(1.0))
                     .with_rate_func(EaseType::Instance)
@@ -24,24 +68,26 @@ pub trait WithPath: WithId {
             ],
         }
     }
-    fn fade_out(&self) -> EntityAnimations {
+    /// Same as [WithPath::fade_out].
+    pub fn fade_out(entity: impl Into<Entity>) -> Self {
         EntityAnimations {
-            entity: self.id(),
+            entity: entity.into(),
             animations: Animation::to(Opacity(0.0)).into(),
         }
     }
-    fn morph(&self, entity: impl Into<Entity>) -> EntityAnimations {
-        let entity: Entity = entity.into();
+    /// Same as [WithPath::morph].
+    pub fn morph(entity: impl Into<Entity>, target: impl Into<Entity>) -> Self {
+        let target: Entity = target.into();
         EntityAnimations {
-            entity: self.id(),
+            entity: entity.into(),
             animations: vec![
-                Animation::<Path>::to_target(entity).into(),
-                Animation::<Angle>::to_target(entity).into(),
-                Animation::<Scale>::to_target(entity).into(),
-                Animation::<StrokeWeight>::to_target(entity).into(),
-                Animation::<StrokeColor>::to_target(entity).into(),
-                Animation::<FillColor>::to_target(entity).into(),
-                Animation::<Position>::to_target(entity).into(),
+                Animation::<Path>::to_target(target).into(),
+                Animation::<Angle>::to_target(target).into(),
+                Animation::<Scale>::to_target(target).into(),
+                Animation::<StrokeWeight>::to_target(target).into(),
+                Animation::<StrokeColor>::to_target(target).into(),
+                Animation::<FillColor>::to_target(target).into(),
+                Animation::<Position>::to_target(target).into(),
             ],
         }
     }
@@ -86,8 +86,43 @@ pub trait WithSize: WithId {
     }
 }
 
+pub trait WithArc: WithId {
+    /// Animate the angle at which the arc begins, keeping its sweep.
+    fn set_start_angle(&self, radians: f32) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: vec![Animation::to(StartAngle(radians)).into()],
+        }
+    }
+    /// Animate the angle covered by the arc, e.g. to sweep it open from zero.
+    fn set_sweep_angle(&self, radians: f32) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: vec![Animation::to(SweepAngle(radians)).into()],
+        }
+    }
+    /// Animate the outer radius of a circular arc.
+    fn set_radius(&self, radius: f32) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: vec![Animation::to(Size::from_radius(radius)).into()],
+        }
+    }
+}
+
+pub trait WithInnerRadius: WithId {
+    fn set_inner_radius(&self, radius: f32) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.id(),
+            animations: vec![Animation::to(InnerRadius(radius)).into()],
+        }
+    }
+}
+
 pub trait WithFontSize: WithId {
-    fn set_font_size(&self, size: u32) -> EntityAnimations {
+    /// Animate the height of a line of text to `size` in scene units.
+    /// The text is laid out again on every frame of the animation.
+    fn set_font_size(&self, size: f32) -> EntityAnimations {
         EntityAnimations {
             entity: self.id(),
             animations: vec![Animation::to(FontSize(size)).into()],
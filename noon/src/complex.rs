@@ -0,0 +1,177 @@
+use crate::{point, Point};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Complex number, used to describe conformal maps of the plane with
+/// [WithPath::apply_complex_function](crate::WithPath::apply_complex_function).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: f32,
+    pub im: f32,
+}
+
+impl Complex {
+    pub const I: Self = Self { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f32, im: f32) -> Self {
+        Self { re, im }
+    }
+    /// Complex number with given modulus and argument.
+    pub fn from_polar(r: f32, theta: f32) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+    pub fn norm(&self) -> f32 {
+        self.re.hypot(self.im)
+    }
+    pub fn arg(&self) -> f32 {
+        self.im.atan2(self.re)
+    }
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+    pub fn exp(&self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+    /// Principal value of the natural logarithm.
+    pub fn ln(&self) -> Self {
+        Self::new(self.norm().ln(), self.arg())
+    }
+    /// Principal value of `self` raised to a real power.
+    pub fn powf(&self, n: f32) -> Self {
+        Self::from_polar(self.norm().powf(n), self.arg() * n)
+    }
+    pub fn powi(&self, n: i32) -> Self {
+        let mut result = Self::new(1.0, 0.0);
+        for _ in 0..n.unsigned_abs() {
+            result = result * *self;
+        }
+        if n < 0 {
+            Self::new(1.0, 0.0) / result
+        } else {
+            result
+        }
+    }
+    pub fn sqrt(&self) -> Self {
+        self.powf(0.5)
+    }
+    pub fn sin(&self) -> Self {
+        Self::new(
+            self.re.sin() * self.im.cosh(),
+            self.re.cos() * self.im.sinh(),
+        )
+    }
+    pub fn cos(&self) -> Self {
+        Self::new(
+            self.re.cos() * self.im.cosh(),
+            -self.re.sin() * self.im.sinh(),
+        )
+    }
+}
+
+impl From<f32> for Complex {
+    fn from(re: f32) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl From<Point> for Complex {
+    fn from(p: Point) -> Self {
+        Self::new(p.x, p.y)
+    }
+}
+
+impl From<Complex> for Point {
+    fn from(z: Complex) -> Self {
+        point(z.re, z.im)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        let d = other.re * other.re + other.im * other.im;
+        Self::new(
+            (self.re * other.re + self.im * other.im) / d,
+            (self.im * other.re - self.re * other.im) / d,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Add<f32> for Complex {
+    type Output = Self;
+    fn add(self, other: f32) -> Self {
+        Self::new(self.re + other, self.im)
+    }
+}
+
+impl Sub<f32> for Complex {
+    type Output = Self;
+    fn sub(self, other: f32) -> Self {
+        Self::new(self.re - other, self.im)
+    }
+}
+
+impl Mul<f32> for Complex {
+    type Output = Self;
+    fn mul(self, other: f32) -> Self {
+        Self::new(self.re * other, self.im * other)
+    }
+}
+
+impl Div<f32> for Complex {
+    type Output = Self;
+    fn div(self, other: f32) -> Self {
+        Self::new(self.re / other, self.im / other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Complex, b: Complex) -> bool {
+        (a - b).norm() < 1.0e-5
+    }
+
+    #[test]
+    fn arithmetic() {
+        let z = Complex::new(1.0, 2.0);
+        assert!(close(z * z, Complex::new(-3.0, 4.0)));
+        assert!(close(z * z / z, z));
+        assert!(close(Complex::I * Complex::I, Complex::from(-1.0)));
+        assert!(close(z.powi(3), z * z * z));
+        assert!(close(z.powi(-1) * z, Complex::from(1.0)));
+        assert!(close(z.sqrt() * z.sqrt(), z));
+        assert!(close(z.ln().exp(), z));
+    }
+}
@@ -4,6 +4,7 @@ use bevy_ecs::prelude::*;
 use nannou::color::{IntoLinSrgba, LinSrgba};
 use nannou::lyon::math as euclid;
 use std::ops::{Add, Mul};
+use std::sync::Arc;
 
 pub trait Interpolate<T = Self> {
     fn interp(&self, other: &T, progress: f32) -> Self
@@ -20,7 +21,10 @@ impl Interpolate for f32 {
 
 impl Interpolate for u32 {
     fn interp(&self, other: &Self, progress: f32) -> Self {
-        self + ((other - self) as f32 * progress) as u32
+        (*self as f32)
+            .interp(&(*other as f32), progress)
+            .round()
+            .max(0.0) as u32
     }
 }
 
@@ -37,6 +41,12 @@ impl Transform {
     pub fn identity() -> Self {
         Self(euclid::Transform::identity())
     }
+    /// Linear map sending `(x, y)` to `(a x + b y, c x + d y)` for the
+    /// matrix `[[a, b], [c, d]]`.
+    pub fn from_matrix(matrix: [[f32; 2]; 2]) -> Self {
+        let [[a, b], [c, d]] = matrix;
+        Self(euclid::Transform::new(a, c, b, d, 0.0, 0.0))
+    }
     /// Translation. Untested
     pub fn translate(mut self, vector: Vector) -> Self {
         self.translate_mut(vector);
@@ -67,6 +77,13 @@ impl Transform {
     pub fn transform(self, transform: Transform) -> Self {
         Self(self.0.then(&transform.0))
     }
+    /// Inverse transform, if there is one.
+    pub fn inverse(&self) -> Option<Self> {
+        self.0.inverse().map(Self)
+    }
+    pub fn transform_point(&self, p: Point) -> Point {
+        self.0.transform_point(p)
+    }
 }
 
 #[derive(Debug, Component, Default, Clone)]
@@ -111,7 +128,7 @@ impl Interpolate for Scale {
     }
 }
 
-#[derive(Debug, Component, Default, Clone, Copy)]
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
@@ -175,7 +192,7 @@ impl PixelFrame for Position {
     }
 }
 
-#[derive(Debug, Component, Default, Clone, Copy)]
+#[derive(Debug, Component, Default, Clone, Copy, PartialEq)]
 pub struct Angle(pub(crate) f32);
 
 impl Interpolate for Angle {
@@ -200,8 +217,9 @@ impl Interpolate for Depth {
     }
 }
 
+/// Height of a line of text in scene units.
 #[derive(Debug, Component, Clone, Copy)]
-pub struct FontSize(pub(crate) u32);
+pub struct FontSize(pub(crate) f32);
 
 impl Interpolate for FontSize {
     fn interp(&self, other: &Self, progress: f32) -> Self {
@@ -216,6 +234,59 @@ impl Add for FontSize {
     }
 }
 
+/// Angle in radians at which an arc or sector begins, measured
+/// counter-clockwise from the positive x axis.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct StartAngle(pub(crate) f32);
+
+impl Interpolate for StartAngle {
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        Self(self.0.interp(&other.0, progress))
+    }
+}
+
+impl Add for StartAngle {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+/// Angle in radians covered by an arc or sector. Positive angles sweep
+/// counter-clockwise.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct SweepAngle(pub(crate) f32);
+
+impl Interpolate for SweepAngle {
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        Self(self.0.interp(&other.0, progress))
+    }
+}
+
+impl Add for SweepAngle {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+/// Inner radius of a sector or annulus in scene units.
+#[derive(Debug, Component, Default, Clone, Copy)]
+pub struct InnerRadius(pub(crate) f32);
+
+impl Interpolate for InnerRadius {
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        Self(self.0.interp(&other.0, progress))
+    }
+}
+
+impl Add for InnerRadius {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
 #[derive(Debug, Component, Default, Clone, Copy)]
 pub struct StrokeWeight(pub(crate) f32);
 
@@ -349,6 +420,40 @@ pub enum Value<C> {
     From(Entity),
 }
 
+/// Function mapping points of the scene to new points, used for animating
+/// pointwise deformations of paths.
+#[derive(Clone)]
+pub struct PointMap {
+    f: Arc<dyn Fn(Point) -> Point + Send + Sync>,
+    /// Affine maps send Bezier curves to Bezier curves, so they can be
+    /// applied to control points directly. Other paths are resampled first.
+    pub(crate) affine: bool,
+}
+
+impl PointMap {
+    pub fn new(f: impl Fn(Point) -> Point + Send + Sync + 'static) -> Self {
+        Self {
+            f: Arc::new(f),
+            affine: false,
+        }
+    }
+    pub fn affine(transform: Transform) -> Self {
+        Self {
+            f: Arc::new(move |p| transform.transform_point(p)),
+            affine: true,
+        }
+    }
+    pub fn apply(&self, p: Point) -> Point {
+        (self.f)(p)
+    }
+}
+
+impl std::fmt::Debug for PointMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PointMap")
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct Previous<T>(pub(crate) T);
 
@@ -417,3 +522,23 @@ pub struct Previous<T>(pub(crate) T);
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolate_u32_both_ways() {
+        assert_eq!(10u32.interp(&20, 0.5), 15);
+        assert_eq!(20u32.interp(&10, 0.5), 15);
+        assert_eq!(20u32.interp(&0, 1.0), 0);
+    }
+
+    #[test]
+    fn values_of_copy_components_are_copy() {
+        let value = Value::Relative(Position { x: 1.0, y: 2.0 });
+        let copy = value;
+        assert!(matches!(value, Value::Relative(p) if p.x == 1.0));
+        assert!(matches!(copy, Value::Relative(p) if p.y == 2.0));
+    }
+}
@@ -0,0 +1,190 @@
+use bevy_ecs::prelude::*;
+use nannou::text::{font, Font};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub use nannou::text::font::Error as FontError;
+
+/// Identifier of a font registered in [Fonts].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FontId(pub(crate) usize);
+
+/// Fonts used by a text object in order of preference.
+///
+/// Characters missing from the first font are looked up in the next one,
+/// and so on. The default font of [Fonts] is always tried last.
+#[derive(Debug, Component, Clone, Default)]
+pub struct FontFamily(pub(crate) Vec<FontId>);
+
+impl FontFamily {
+    pub fn new(font: FontId) -> Self {
+        Self(vec![font])
+    }
+    /// Add a font to fall back to for missing glyphs.
+    pub fn add(&mut self, font: FontId) {
+        self.0.push(font);
+    }
+}
+
+/// Weight and slant of a font, used to pick font variants for styled
+/// ranges of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FontStyle {
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl FontStyle {
+    pub const REGULAR: Self = Self {
+        bold: false,
+        italic: false,
+    };
+    pub const BOLD: Self = Self {
+        bold: true,
+        italic: false,
+    };
+    pub const ITALIC: Self = Self {
+        bold: false,
+        italic: true,
+    };
+    pub const BOLD_ITALIC: Self = Self {
+        bold: true,
+        italic: true,
+    };
+}
+
+/// Scene-level registry of fonts available to text objects.
+///
+/// The registry starts with nannou's default font, which is used for
+/// any text object that doesn't specify a [FontFamily].
+#[derive(Resource, Clone)]
+pub struct Fonts {
+    fonts: Vec<Font>,
+    default: FontId,
+    variants: HashMap<(FontId, FontStyle), FontId>,
+}
+
+impl Default for Fonts {
+    fn default() -> Self {
+        Self {
+            fonts: vec![font::default_notosans()],
+            default: FontId(0),
+            variants: HashMap::new(),
+        }
+    }
+}
+
+impl Fonts {
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Register a font loaded from a font file (e.g. `.ttf` or `.otf`).
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<FontId, FontError> {
+        Ok(self.insert(font::from_file(path)?))
+    }
+    /// Register a font from the raw bytes of a font file.
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<FontId, FontError> {
+        let font = Font::from_bytes(bytes.to_vec()).map_err(|_| FontError::NoFont)?;
+        Ok(self.insert(font))
+    }
+    /// Register an already loaded font.
+    pub fn insert(&mut self, font: Font) -> FontId {
+        self.fonts.push(font);
+        FontId(self.fonts.len() - 1)
+    }
+    pub fn get(&self, id: FontId) -> Option<&Font> {
+        self.fonts.get(id.0)
+    }
+    /// Font used when a text object doesn't specify one, or as the
+    /// last resort for missing glyphs.
+    pub fn default_font(&self) -> FontId {
+        self.default
+    }
+    pub fn set_default_font(&mut self, id: FontId) {
+        if id.0 < self.fonts.len() {
+            self.default = id;
+        }
+    }
+    /// Resolve the given [FontFamily] into a list of fonts, ending with
+    /// the default font.
+    pub fn chain(&self, family: &FontFamily) -> Vec<Font> {
+        family
+            .0
+            .iter()
+            .chain(std::iter::once(&self.default))
+            .filter_map(|&id| self.get(id).cloned())
+            .collect()
+    }
+    /// Register `variant` as the bold, italic or bold italic version of
+    /// `font`, used for the styled ranges of texts set in `font`.
+    pub fn set_variant(&mut self, font: FontId, style: FontStyle, variant: FontId) {
+        if variant.0 < self.fonts.len() && style != FontStyle::REGULAR {
+            self.variants.insert((font, style), variant);
+        }
+    }
+    /// Version of `font` registered for `style`, if any.
+    pub fn variant(&self, font: FontId, style: FontStyle) -> Option<FontId> {
+        self.variants.get(&(font, style)).copied()
+    }
+    /// Resolve the given [FontFamily] into a list of fonts for `style`,
+    /// replacing each font with its variant when registered.
+    ///
+    /// Bold italic falls back to the bold variant. Also returns whether the
+    /// first font lacks an italic variant, in which case glyphs have to be
+    /// slanted to look italic.
+    pub(crate) fn chain_styled(&self, family: &FontFamily, style: FontStyle) -> (Vec<Font>, bool) {
+        let mut slanted = style.italic;
+        let fonts = family
+            .0
+            .iter()
+            .chain(std::iter::once(&self.default))
+            .enumerate()
+            .filter_map(|(i, &id)| {
+                let (variant, italic) = match self.variant(id, style) {
+                    Some(variant) => (variant, style.italic),
+                    None if style.bold => (self.variant(id, FontStyle::BOLD).unwrap_or(id), false),
+                    None => (id, false),
+                };
+                if i == 0 && italic {
+                    slanted = false;
+                }
+                self.get(variant).cloned()
+            })
+            .collect();
+        (fonts, slanted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_ends_with_default() {
+        let mut fonts = Fonts::new();
+        let id = fonts.insert(font::default_notosans());
+        assert_eq!(id, FontId(1));
+
+        let family = FontFamily::new(id);
+        assert_eq!(fonts.chain(&family).len(), 2);
+        assert_eq!(fonts.chain(&FontFamily::default()).len(), 1);
+    }
+
+    #[test]
+    fn variants_replace_fonts() {
+        let mut fonts = Fonts::new();
+        let bold = fonts.insert(font::default_notosans());
+        fonts.set_variant(fonts.default_font(), FontStyle::BOLD, bold);
+        let family = FontFamily::default();
+        assert_eq!(fonts.variant(FontId(0), FontStyle::BOLD), Some(bold));
+        assert!(!fonts.chain_styled(&family, FontStyle::BOLD).1);
+        // Without an italic variant, glyphs of bold italic text get slanted.
+        assert!(fonts.chain_styled(&family, FontStyle::BOLD_ITALIC).1);
+    }
+
+    #[test]
+    fn invalid_bytes_are_rejected() {
+        let mut fonts = Fonts::new();
+        assert!(fonts.load_bytes(&[0, 1, 2, 3]).is_err());
+    }
+}
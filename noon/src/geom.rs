@@ -71,7 +71,7 @@ impl BoundingSize {
 }
 
 /// Data type to represent physical size of any 2D object.
-#[derive(Debug, Component, Clone, Copy)]
+#[derive(Debug, Component, Clone, Copy, PartialEq)]
 pub struct Size {
     pub width: f32,
     pub height: f32,
@@ -1,9 +1,11 @@
 mod animation;
 // mod app;
 pub mod color;
+pub mod complex;
 pub mod component;
 pub mod consts;
 pub mod ease;
+pub mod font;
 pub mod geom;
 pub mod object;
 pub mod path;
@@ -11,21 +13,24 @@ pub mod scene;
 pub mod system;
 
 pub use crate::animation::{
-    AnimBuilder, Animation, AnimationType, Animations, Create, EntityAnimations, WithAngle,
-    WithColor, WithFill, WithFontSize, WithId, WithPath, WithPosition, WithSize, WithStroke,
-    WithStrokeWeight,
+    AnimBuilder, Animation, AnimationType, Animations, Create, EntityAnimations, PathMaps,
+    WithAngle, WithArc, WithColor, WithFill, WithFontSize, WithId, WithInnerRadius, WithPath,
+    WithPosition, WithSize, WithStroke, WithStrokeWeight, WithTextColor,
 };
 
 pub use crate::color::{Color, ColorExtension};
+pub use crate::complex::Complex;
 pub use crate::component::{
-    Angle, Depth, FillColor, FontSize, HasFill, Interpolate, Name, Opacity, PathCompletion,
-    Position, Previous, Scale, StrokeColor, StrokeWeight, Transform, Value,
+    Angle, Depth, FillColor, FontSize, HasFill, InnerRadius, Interpolate, Name, Opacity,
+    PathCompletion, PointMap, Position, Previous, Scale, StartAngle, StrokeColor, StrokeWeight,
+    SweepAngle, Transform, Value,
 };
 
 pub use crate::geom::{point, BoundingSize, PixelFrame, Point, Size, Vector};
-pub use crate::path::{GetPartial, Path, PathComponent, PixelPath};
+pub use crate::path::{GetPartial, Path, PathComponent, PixelPath, SvgPathError};
 pub use consts::*;
 pub use ease::EaseType;
+pub use font::{FontError, FontFamily, FontId, FontStyle, Fonts};
 pub use object::*;
 pub use scene::{Bounds, Scene};
 pub use system::{animate, init_from_target, print, update_time, Time};
@@ -35,9 +40,9 @@ pub use nannou::{app, rand};
 
 pub mod prelude {
     pub use crate::animation::{
-        AnimBuilder, Animation, AnimationType, Animations, Create, EntityAnimations, WithAngle,
-        WithColor, WithFill, WithFontSize, WithId, WithPath, WithPosition, WithSize, WithStroke,
-        WithStrokeWeight,
+        AnimBuilder, Animation, AnimationType, Animations, Create, EntityAnimations, PathMaps,
+        WithAngle, WithArc, WithColor, WithFill, WithFontSize, WithId, WithInnerRadius, WithPath,
+        WithPosition, WithSize, WithStroke, WithStrokeWeight, WithTextColor,
     };
     pub use crate::consts::*;
     pub use crate::{
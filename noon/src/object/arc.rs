@@ -0,0 +1,381 @@
+use super::common::*;
+use core::f32::consts::{FRAC_PI_2, TAU};
+use nannou::lyon::math::{Angle as LyonAngle, Vector};
+
+/// Component indicating an open arc of a circle or an ellipse.
+///
+/// The radii of the arc are given by its [Size], while the part of the
+/// ellipse that is drawn is given by [StartAngle] and [SweepAngle].
+#[derive(Component)]
+pub struct Arc;
+
+/// Component indicating a filled sector of a circle or an ellipse. With a
+/// non-zero [InnerRadius], the sector becomes an annular sector, and with a
+/// full sweep it becomes an annulus.
+#[derive(Component)]
+pub struct Sector;
+
+/// Angles closer than this to a full turn are drawn as closed ellipses.
+const FULL_TURN: f32 = TAU - 1.0e-4;
+
+fn on_ellipse(radii: Vector, angle: f32) -> Point {
+    point(radii.x * angle.cos(), radii.y * angle.sin())
+}
+
+impl Arc {
+    pub fn path(&self, size: &Size, start: StartAngle, sweep: SweepAngle) -> Path {
+        let radii = Vector::new(size.width / 2.0, size.height / 2.0);
+        let sweep = sweep.0.clamp(-TAU, TAU);
+        let x_rotation = LyonAngle::radians(0.0);
+
+        let mut builder = Path::svg_builder();
+        builder.move_to(on_ellipse(radii, start.0));
+        builder.arc(
+            point(0.0, 0.0),
+            radii,
+            LyonAngle::radians(sweep),
+            x_rotation,
+        );
+        if sweep.abs() >= FULL_TURN {
+            builder.close();
+        }
+
+        Path::new(builder.build(), sweep.abs() >= FULL_TURN)
+    }
+}
+
+impl Sector {
+    pub fn path(
+        &self,
+        size: &Size,
+        start: StartAngle,
+        sweep: SweepAngle,
+        inner: InnerRadius,
+    ) -> Path {
+        let center = point(0.0, 0.0);
+        let outer = Vector::new(size.width / 2.0, size.height / 2.0);
+        // Inner ellipse has the same proportions as the outer one.
+        let ratio = if outer.x > 0.0 {
+            (inner.0 / outer.x).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let inner = outer * ratio;
+        let start = start.0;
+        let sweep = sweep.0.clamp(-TAU, TAU);
+        let x_rotation = LyonAngle::radians(0.0);
+
+        let mut builder = Path::svg_builder();
+        if sweep.abs() >= FULL_TURN {
+            builder.move_to(on_ellipse(outer, start));
+            builder.arc(center, outer, LyonAngle::radians(TAU), x_rotation);
+            builder.close();
+            if ratio > 0.0 {
+                // Opposite winding so that the hole is left unfilled.
+                builder.move_to(on_ellipse(inner, start));
+                builder.arc(center, inner, LyonAngle::radians(-TAU), x_rotation);
+                builder.close();
+            }
+        } else if ratio > 0.0 {
+            builder.move_to(on_ellipse(outer, start));
+            builder.arc(center, outer, LyonAngle::radians(sweep), x_rotation);
+            builder.line_to(on_ellipse(inner, start + sweep));
+            builder.arc(center, inner, LyonAngle::radians(-sweep), x_rotation);
+            builder.close();
+        } else {
+            builder.move_to(center);
+            builder.line_to(on_ellipse(outer, start));
+            builder.arc(center, outer, LyonAngle::radians(sweep), x_rotation);
+            builder.close();
+        }
+
+        Path::new(builder.build(), true)
+    }
+}
+
+pub struct ArcBuilder<'a> {
+    size: Size,
+    start: StartAngle,
+    sweep: SweepAngle,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    position: Position,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> ArcBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        Self {
+            size: Size::from_radius(0.5),
+            start: StartAngle(0.0),
+            sweep: SweepAngle(FRAC_PI_2),
+            stroke_weight: StrokeWeight::THICK,
+            stroke_color: Color::random(),
+            position: Default::default(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.size = Size::from_radius(radius);
+        self
+    }
+    /// Make the arc part of an ellipse with given horizontal and vertical radii.
+    pub fn with_radii(mut self, x: f32, y: f32) -> Self {
+        self.size = Size::from(x * 2.0, y * 2.0);
+        self
+    }
+    pub fn with_start_angle(mut self, radians: f32) -> Self {
+        self.start = StartAngle(radians);
+        self
+    }
+    pub fn with_sweep_angle(mut self, radians: f32) -> Self {
+        self.sweep = SweepAngle(radians);
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+}
+
+crate::angle_builder!(ArcBuilder);
+crate::stroke_builder!(ArcBuilder);
+crate::position_builder!(ArcBuilder);
+
+impl Create<ArcId> for ArcBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> ArcId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let path = Arc.path(&self.size, self.start, self.sweep);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(self.angle)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(Arc)
+            .insert(self.size)
+            .insert(BoundingSize(self.size))
+            .insert(self.start)
+            .insert(self.sweep)
+            .insert(scale)
+            .insert(self.position)
+            .insert(self.angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.stroke_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(false))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn arc(scene: &mut Scene) -> ArcBuilder<'_> {
+    ArcBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct ArcId(pub(crate) Entity);
+crate::into_entity!(ArcId);
+
+impl WithStroke for ArcId {}
+impl WithColor for ArcId {}
+impl WithPath for ArcId {}
+impl WithPosition for ArcId {}
+impl WithAngle for ArcId {}
+impl WithSize for ArcId {}
+impl WithStrokeWeight for ArcId {}
+impl WithArc for ArcId {}
+
+pub struct SectorBuilder<'a> {
+    size: Size,
+    start: StartAngle,
+    sweep: SweepAngle,
+    inner: InnerRadius,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    position: Position,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> SectorBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        let fill_color = Color::random();
+        Self {
+            size: Size::from_radius(0.5),
+            start: StartAngle(0.0),
+            sweep: SweepAngle(FRAC_PI_2),
+            inner: InnerRadius(0.0),
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            position: Default::default(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.size = Size::from_radius(radius);
+        self
+    }
+    /// Make the sector part of an ellipse with given horizontal and vertical radii.
+    pub fn with_radii(mut self, x: f32, y: f32) -> Self {
+        self.size = Size::from(x * 2.0, y * 2.0);
+        self
+    }
+    /// Cut out the center of the sector up to the given radius.
+    pub fn with_inner_radius(mut self, radius: f32) -> Self {
+        self.inner = InnerRadius(radius);
+        self
+    }
+    pub fn with_start_angle(mut self, radians: f32) -> Self {
+        self.start = StartAngle(radians);
+        self
+    }
+    pub fn with_sweep_angle(mut self, radians: f32) -> Self {
+        self.sweep = SweepAngle(radians);
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::angle_builder!(SectorBuilder);
+crate::stroke_builder!(SectorBuilder);
+crate::position_builder!(SectorBuilder);
+crate::fill_builder!(SectorBuilder);
+
+impl Create<SectorId> for SectorBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> SectorId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let path = Sector.path(&self.size, self.start, self.sweep, self.inner);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(self.angle)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(Sector)
+            .insert(self.size)
+            .insert(BoundingSize(self.size))
+            .insert(self.start)
+            .insert(self.sweep)
+            .insert(self.inner)
+            .insert(scale)
+            .insert(self.position)
+            .insert(self.angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.fill_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(true))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn sector(scene: &mut Scene) -> SectorBuilder<'_> {
+    SectorBuilder::new(scene)
+}
+
+/// Ring between the given inner and outer radius.
+pub fn annulus(scene: &mut Scene, inner: f32, outer: f32) -> SectorBuilder<'_> {
+    SectorBuilder::new(scene)
+        .with_radius(outer)
+        .with_inner_radius(inner)
+        .with_sweep_angle(TAU)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct SectorId(pub(crate) Entity);
+crate::into_entity!(SectorId);
+
+impl WithStroke for SectorId {}
+impl WithFill for SectorId {}
+impl WithColor for SectorId {}
+impl WithPath for SectorId {}
+impl WithPosition for SectorId {}
+impl WithAngle for SectorId {}
+impl WithSize for SectorId {}
+impl WithStrokeWeight for SectorId {}
+impl WithArc for SectorId {}
+impl WithInnerRadius for SectorId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarter_sector_fills_one_quadrant() {
+        let size = Sector
+            .path(
+                &Size::from_radius(0.5),
+                StartAngle(0.0),
+                SweepAngle(FRAC_PI_2),
+                InnerRadius(0.0),
+            )
+            .size();
+        assert!((size.width - 0.5).abs() < 1.0e-3);
+        assert!((size.height - 0.5).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn annulus_has_inner_and_outer_rings() {
+        use nannou::lyon::path::PathEvent;
+        let path = Sector.path(
+            &Size::from_radius(1.0),
+            StartAngle(0.0),
+            SweepAngle(TAU),
+            InnerRadius(0.5),
+        );
+        let rings = path
+            .raw
+            .iter()
+            .filter(|e| matches!(e, PathEvent::Begin { .. }))
+            .count();
+        assert_eq!(rings, 2);
+    }
+}
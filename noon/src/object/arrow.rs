@@ -0,0 +1,338 @@
+use super::common::*;
+use nannou::lyon::math::{Angle as LyonAngle, Vector};
+
+/// Shape of the tip drawn at the end(s) of an [Arrow].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TipStyle {
+    /// Filled isosceles triangle.
+    Triangle,
+    /// Filled dart with a notch at the back.
+    Stealth,
+    /// Two lines meeting at the tip, without any fill.
+    Open,
+}
+
+/// Component indicating an arrow.
+///
+/// The arrow is laid out along the x axis and centered at the origin, with
+/// its length given by the width of its [Size]. The tip keeps the same size
+/// as the length changes, unless it would exceed `max_tip_ratio` of the
+/// arrow length.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Arrow {
+    pub tip: TipStyle,
+    pub tip_length: f32,
+    pub tip_width: f32,
+    /// Whether to draw a tip at the start of the arrow as well.
+    pub double: bool,
+    /// Angle subtended by the shaft when drawn as a circular arc. Positive
+    /// angles bend the arrow to the left, and `0.0` gives a straight arrow.
+    pub arc_angle: f32,
+    /// Maximum ratio of the tip length to the arrow length.
+    pub max_tip_ratio: f32,
+}
+
+impl Default for Arrow {
+    fn default() -> Self {
+        Self {
+            tip: TipStyle::Triangle,
+            tip_length: 0.25,
+            tip_width: 0.2,
+            double: false,
+            arc_angle: 0.0,
+            max_tip_ratio: 0.25,
+        }
+    }
+}
+
+impl Arrow {
+    /// Fraction of the tip length covered by the shaft, i.e. how far back from
+    /// the tip the shaft stops.
+    fn inset(&self) -> f32 {
+        match self.tip {
+            TipStyle::Triangle => 1.0,
+            TipStyle::Stealth => 0.6,
+            TipStyle::Open => 0.0,
+        }
+    }
+
+    /// Add a tip with apex `at` pointing towards `direction`.
+    fn add_tip(
+        &self,
+        builder: &mut WithSvg,
+        at: Point,
+        direction: Vector,
+        length: f32,
+        width: f32,
+    ) {
+        let normal = Vector::new(-direction.y, direction.x) * width / 2.0;
+        let base = at - direction * length;
+        match self.tip {
+            TipStyle::Triangle => {
+                builder.move_to(at);
+                builder.line_to(base + normal);
+                builder.line_to(base - normal);
+                builder.close();
+            }
+            TipStyle::Stealth => {
+                builder.move_to(at);
+                builder.line_to(base + normal);
+                builder.line_to(at - direction * length * self.inset());
+                builder.line_to(base - normal);
+                builder.close();
+            }
+            TipStyle::Open => {
+                builder.move_to(base + normal);
+                builder.line_to(at);
+                builder.line_to(base - normal);
+            }
+        }
+    }
+}
+
+type WithSvg = nannou::lyon::path::builder::WithSvg<nannou::lyon::path::path::Builder>;
+
+impl PathComponent for Arrow {
+    fn path(&self, size: &Size) -> Path {
+        let length = size.width.max(0.0);
+        let tip_length = self.tip_length.min(self.max_tip_ratio * length);
+        let tip_width = if self.tip_length > 0.0 {
+            self.tip_width * tip_length / self.tip_length
+        } else {
+            0.0
+        };
+        let inset = tip_length * self.inset();
+        let start_inset = if self.double { inset } else { 0.0 };
+
+        let mut builder = Path::svg_builder();
+        let start = point(-length / 2.0, 0.0);
+        let end = point(length / 2.0, 0.0);
+
+        if self.arc_angle.abs() < 1.0e-3 {
+            builder.move_to(start + Vector::new(start_inset, 0.0));
+            builder.line_to(end - Vector::new(inset, 0.0));
+
+            self.add_tip(
+                &mut builder,
+                end,
+                Vector::new(1.0, 0.0),
+                tip_length,
+                tip_width,
+            );
+            if self.double {
+                self.add_tip(
+                    &mut builder,
+                    start,
+                    Vector::new(-1.0, 0.0),
+                    tip_length,
+                    tip_width,
+                );
+            }
+        } else {
+            let half = self.arc_angle / 2.0;
+            let radius = (length / 2.0) / half.sin().abs();
+            let center = point(0.0, -(length / 2.0) / half.tan());
+            let direction = -self.arc_angle.signum();
+            let begin = self.arc_angle.signum() * std::f32::consts::FRAC_PI_2 + half;
+            let finish = begin - self.arc_angle;
+            let at = |angle: f32| center + Vector::new(angle.cos(), angle.sin()) * radius;
+            let tangent = |angle: f32| Vector::new(-angle.sin(), angle.cos()) * direction;
+
+            let shaft_begin = begin + direction * start_inset / radius;
+            let shaft_end = finish - direction * inset / radius;
+            let sweep = LyonAngle::radians(shaft_end - shaft_begin);
+            let radii = Vector::new(radius, radius);
+            let x_rotation = LyonAngle::radians(0.0);
+
+            // Trace the arc back onto itself so that filling the tips doesn't
+            // also fill the area between the arc and its chord.
+            builder.move_to(at(shaft_begin));
+            builder.arc(center, radii, sweep, x_rotation);
+            builder.arc(center, radii, -sweep, x_rotation);
+            builder.close();
+
+            self.add_tip(&mut builder, end, tangent(finish), tip_length, tip_width);
+            if self.double {
+                self.add_tip(&mut builder, start, -tangent(begin), tip_length, tip_width);
+            }
+        }
+
+        Path::new(builder.build(), false)
+    }
+}
+
+pub struct ArrowBuilder<'a> {
+    from: Point,
+    to: Point,
+    arrow: Arrow,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    scene: &'a mut Scene,
+}
+
+impl<'a> ArrowBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        Self {
+            from: point(0.0, 0.0),
+            to: point(1.0, 0.0),
+            arrow: Default::default(),
+            stroke_weight: StrokeWeight::THICK,
+            stroke_color: Color::random(),
+            scene,
+        }
+    }
+    pub fn from(mut self, x: f32, y: f32) -> Self {
+        self.from = point(x, y);
+        self
+    }
+    pub fn to(mut self, x: f32, y: f32) -> Self {
+        self.to = point(x, y);
+        self
+    }
+    pub fn with_tip(mut self, tip: TipStyle) -> Self {
+        self.arrow.tip = tip;
+        self
+    }
+    /// Size of the tip in scene units, along and across the arrow.
+    pub fn with_tip_size(mut self, length: f32, width: f32) -> Self {
+        self.arrow.tip_length = length;
+        self.arrow.tip_width = width;
+        self
+    }
+    /// Draw a tip at both ends of the arrow.
+    pub fn double_headed(mut self) -> Self {
+        self.arrow.double = true;
+        self
+    }
+    /// Bend the arrow into a circular arc subtending the given angle.
+    pub fn with_arc_angle(mut self, radians: f32) -> Self {
+        self.arrow.arc_angle = radians;
+        self
+    }
+    /// Limit the tip length to the given ratio of the arrow length.
+    pub fn with_max_tip_ratio(mut self, ratio: f32) -> Self {
+        self.arrow.max_tip_ratio = ratio;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+}
+
+crate::stroke_builder!(ArrowBuilder);
+
+/// Position, angle and size placing an arrow between two points.
+fn placement(from: Point, to: Point) -> (Position, Angle, Size) {
+    let delta = to - from;
+    (
+        Position::from_points(&[from, to]),
+        Angle(delta.y.atan2(delta.x)),
+        Size::from(delta.length(), 0.0),
+    )
+}
+
+impl Create<ArrowId> for ArrowBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> ArrowId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let (position, angle, size) = placement(self.from, self.to);
+        let path = self.arrow.path(&size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(angle)
+            .translate(position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(self.arrow)
+            .insert(size)
+            .insert(BoundingSize(size))
+            .insert(scale)
+            .insert(position)
+            .insert(angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.stroke_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(self.arrow.tip != TipStyle::Open))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn arrow(scene: &mut Scene) -> ArrowBuilder<'_> {
+    ArrowBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct ArrowId(pub(crate) Entity);
+crate::into_entity!(ArrowId);
+
+impl WithStroke for ArrowId {}
+impl WithFill for ArrowId {}
+impl WithColor for ArrowId {}
+impl WithPath for ArrowId {}
+impl WithPosition for ArrowId {}
+impl WithAngle for ArrowId {}
+impl WithSize for ArrowId {}
+impl WithStrokeWeight for ArrowId {}
+
+impl ArrowId {
+    /// Animate the length of the arrow, keeping its center and direction.
+    pub fn set_length(&self, length: f32) -> EntityAnimations {
+        EntityAnimations {
+            entity: self.0,
+            animations: Animation::to(Size::from(length, 0.0)).into(),
+        }
+    }
+    /// Animate the arrow so that it points from `from` to `to`.
+    pub fn put_between(&self, from: Point, to: Point) -> EntityAnimations {
+        let (position, angle, size) = placement(from, to);
+        EntityAnimations {
+            entity: self.0,
+            animations: vec![
+                Animation::to(position).into(),
+                Animation::to(angle).into(),
+                Animation::to(size).into(),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tip_size_is_constant() {
+        let arrow = Arrow::default();
+        let short = arrow.path(&Size::from(2.0, 0.0)).size();
+        let long = arrow.path(&Size::from(4.0, 0.0)).size();
+        assert!((short.height - arrow.tip_width).abs() < 1.0e-4);
+        assert!((long.height - arrow.tip_width).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn tip_is_limited_by_length() {
+        let arrow = Arrow::default();
+        let size = arrow.path(&Size::from(0.4, 0.0)).size();
+        assert!(size.height < arrow.tip_width);
+    }
+}
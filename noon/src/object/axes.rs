@@ -0,0 +1,559 @@
+use super::common::*;
+use crate::{ArrowId, EmptyId, LineId, TextId, Vector};
+use std::ops::Range;
+
+/// Mapping between data coordinates and points of the scene, as used by
+/// [AxesId].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coordinates {
+    pub x_range: Range<f32>,
+    pub y_range: Range<f32>,
+    /// Scene position of the center of the plotted region.
+    pub center: Point,
+    /// Length in scene units of the whole `x_range` and `y_range`.
+    pub x_length: f32,
+    pub y_length: f32,
+}
+
+impl Coordinates {
+    fn x_unit(&self) -> f32 {
+        self.x_length / (self.x_range.end - self.x_range.start)
+    }
+    fn y_unit(&self) -> f32 {
+        self.y_length / (self.y_range.end - self.y_range.start)
+    }
+    pub fn coords_to_point(&self, x: f32, y: f32) -> Point {
+        let x_mid = (self.x_range.start + self.x_range.end) / 2.0;
+        let y_mid = (self.y_range.start + self.y_range.end) / 2.0;
+        point(
+            self.center.x + (x - x_mid) * self.x_unit(),
+            self.center.y + (y - y_mid) * self.y_unit(),
+        )
+    }
+    /// Affine transform from data coordinates to scene points.
+    pub fn to_scene(&self) -> Transform {
+        let x_mid = (self.x_range.start + self.x_range.end) / 2.0;
+        let y_mid = (self.y_range.start + self.y_range.end) / 2.0;
+        Transform::identity()
+            .translate(Vector::new(-x_mid, -y_mid))
+            .scale(Scale::new(self.x_unit(), self.y_unit()))
+            .translate(self.center.to_vector())
+    }
+    pub fn point_to_coords(&self, p: Point) -> (f32, f32) {
+        let x_mid = (self.x_range.start + self.x_range.end) / 2.0;
+        let y_mid = (self.y_range.start + self.y_range.end) / 2.0;
+        (
+            x_mid + (p.x - self.center.x) / self.x_unit(),
+            y_mid + (p.y - self.center.y) / self.y_unit(),
+        )
+    }
+    /// Data coordinates where the two axes cross: zero if it is within the
+    /// range, or else the end of the range closest to zero.
+    pub fn origin(&self) -> (f32, f32) {
+        (
+            0.0_f32.clamp(self.x_range.start, self.x_range.end),
+            0.0_f32.clamp(self.y_range.start, self.y_range.end),
+        )
+    }
+}
+
+/// Indices `i` of the multiples `i * step` within `range`.
+pub(crate) fn tick_indices(range: &Range<f32>, step: f32) -> Range<i64> {
+    if step <= 0.0 {
+        return 0..0;
+    }
+    let first = (range.start / step - EPS).ceil() as i64;
+    let last = (range.end / step + EPS).floor() as i64;
+    first..last + 1
+}
+
+/// Multiples of `step` within `range`.
+pub(crate) fn tick_values(range: &Range<f32>, step: f32) -> Vec<f32> {
+    tick_indices(range, step).map(|i| i as f32 * step).collect()
+}
+
+/// Data coordinates of a pair of axes and the objects moving along with
+/// them, stored on the group of the axes so that every copy of an [AxesId]
+/// sees the same state.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct AxesState {
+    coords: Coordinates,
+    /// Every object moving along with the axes, with its current position.
+    members: Vec<(Entity, Point)>,
+}
+
+/// Format a tick value with at most two decimals, without trailing zeros.
+pub(crate) fn format_number(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_owned()
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Create a text label whose edge facing away from `direction` touches
+/// `anchor`, e.g. with [DOWN](crate::DOWN) the label hangs below the anchor.
+pub(crate) fn place_label(
+    scene: &mut Scene,
+    text: &str,
+    font_size: f32,
+    color: Color,
+    anchor: Point,
+    direction: Vector,
+) -> TextId {
+    let id = crate::text(scene)
+        .with_text(text)
+        .with_font_size(font_size)
+        .with_color(color)
+        .with_position(anchor.x, anchor.y)
+        .make();
+    let world = scene.world.get_mut();
+    if let Some(size) = world.get::<Size>(id.0).copied() {
+        if let Some(mut position) = world.get_mut::<Position>(id.0) {
+            position.x += direction.x * size.width / 2.0;
+            position.y += direction.y * size.height / 2.0;
+        }
+    }
+    id
+}
+
+/// Builder for a pair of axes with tick marks and numeric labels.
+pub struct AxesBuilder<'a> {
+    x_range: Range<f32>,
+    y_range: Range<f32>,
+    x_length: Option<f32>,
+    y_length: Option<f32>,
+    x_step: f32,
+    y_step: f32,
+    labels: bool,
+    x_title: Option<String>,
+    y_title: Option<String>,
+    font_size: f32,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> AxesBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        Self {
+            x_range: -4.0..4.0,
+            y_range: -2.0..2.0,
+            x_length: None,
+            y_length: None,
+            x_step: 1.0,
+            y_step: 1.0,
+            labels: true,
+            x_title: None,
+            y_title: None,
+            font_size: 0.2,
+            stroke_weight: StrokeWeight(2.0),
+            stroke_color: Color::WHITE,
+            position: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_x_range(mut self, range: Range<f32>) -> Self {
+        self.x_range = range;
+        self
+    }
+    pub fn with_y_range(mut self, range: Range<f32>) -> Self {
+        self.y_range = range;
+        self
+    }
+    /// Length of the x axis in scene units. Defaults to one scene unit per
+    /// unit of data.
+    pub fn with_x_length(mut self, length: f32) -> Self {
+        self.x_length = Some(length);
+        self
+    }
+    /// Length of the y axis in scene units. Defaults to one scene unit per
+    /// unit of data.
+    pub fn with_y_length(mut self, length: f32) -> Self {
+        self.y_length = Some(length);
+        self
+    }
+    /// Distance between tick marks in data units.
+    pub fn with_ticks(mut self, x_step: f32, y_step: f32) -> Self {
+        self.x_step = x_step;
+        self.y_step = y_step;
+        self
+    }
+    pub fn without_labels(mut self) -> Self {
+        self.labels = false;
+        self
+    }
+    pub fn with_titles(mut self, x: &str, y: &str) -> Self {
+        self.x_title = Some(x.to_owned());
+        self.y_title = Some(y.to_owned());
+        self
+    }
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+
+    pub fn make(&mut self) -> AxesId {
+        let coords = Coordinates {
+            x_range: self.x_range.clone(),
+            y_range: self.y_range.clone(),
+            center: point(self.position.x, self.position.y),
+            x_length: self
+                .x_length
+                .unwrap_or(self.x_range.end - self.x_range.start),
+            y_length: self
+                .y_length
+                .unwrap_or(self.y_range.end - self.y_range.start),
+        };
+        let (x0, y0) = coords.origin();
+        let color = self.stroke_color;
+        let weight = self.stroke_weight.0;
+        // Axes extend a little past their range so that the tips don't hide the last ticks.
+        let overshoot = 0.25;
+        let tick = 0.06;
+        let gap = 0.1;
+
+        let x_start = coords.coords_to_point(self.x_range.start, y0);
+        let x_end = coords.coords_to_point(self.x_range.end, y0);
+        let x_axis = crate::arrow(self.scene)
+            .from(x_start.x, x_start.y)
+            .to(x_end.x + overshoot, x_end.y)
+            .with_tip_size(0.2, 0.14)
+            .with_color(color)
+            .with_stroke_weight(weight)
+            .make();
+        let y_start = coords.coords_to_point(x0, self.y_range.start);
+        let y_end = coords.coords_to_point(x0, self.y_range.end);
+        let y_axis = crate::arrow(self.scene)
+            .from(y_start.x, y_start.y)
+            .to(y_end.x, y_end.y + overshoot)
+            .with_tip_size(0.2, 0.14)
+            .with_color(color)
+            .with_stroke_weight(weight)
+            .make();
+
+        let mut ticks = Vec::new();
+        let mut labels = Vec::new();
+        for x in tick_values(&self.x_range, self.x_step) {
+            let p = coords.coords_to_point(x, y0);
+            ticks.push(
+                crate::line(self.scene)
+                    .from(p.x, p.y - tick)
+                    .to(p.x, p.y + tick)
+                    .with_color(color)
+                    .with_stroke_weight(weight)
+                    .make(),
+            );
+            if self.labels && (x - x0).abs() > EPS {
+                let anchor = point(p.x, p.y - tick - gap);
+                labels.push(place_label(
+                    self.scene,
+                    &format_number(x),
+                    self.font_size,
+                    color,
+                    anchor,
+                    crate::DOWN,
+                ));
+            }
+        }
+        for y in tick_values(&self.y_range, self.y_step) {
+            let p = coords.coords_to_point(x0, y);
+            ticks.push(
+                crate::line(self.scene)
+                    .from(p.x - tick, p.y)
+                    .to(p.x + tick, p.y)
+                    .with_color(color)
+                    .with_stroke_weight(weight)
+                    .make(),
+            );
+            if self.labels && (y - y0).abs() > EPS {
+                let anchor = point(p.x - tick - gap, p.y);
+                labels.push(place_label(
+                    self.scene,
+                    &format_number(y),
+                    self.font_size,
+                    color,
+                    anchor,
+                    crate::LEFT,
+                ));
+            }
+        }
+
+        let mut titles = Vec::new();
+        if let Some(title) = &self.x_title {
+            let anchor = point(x_end.x + overshoot, x_end.y - tick - gap);
+            titles.push(place_label(
+                self.scene,
+                title,
+                self.font_size * 1.25,
+                color,
+                anchor,
+                Vector::new(-1.0, -1.0),
+            ));
+        }
+        if let Some(title) = &self.y_title {
+            let anchor = point(y_end.x + tick + gap, y_end.y + overshoot);
+            titles.push(place_label(
+                self.scene,
+                title,
+                self.font_size * 1.25,
+                color,
+                anchor,
+                Vector::new(1.0, -1.0),
+            ));
+        }
+
+        let mut members = vec![x_axis.0, y_axis.0];
+        members.extend(ticks.iter().map(|id| id.0));
+        members.extend(labels.iter().map(|id| id.0));
+        members.extend(titles.iter().map(|id| id.0));
+        let mut group = crate::empty(self.scene);
+        for entity in members.iter() {
+            group = group.add(*entity);
+        }
+        let group = group.with_position(self.position.x, self.position.y).make();
+
+        let world = self.scene.world.get_mut();
+        let members = members
+            .into_iter()
+            .map(|entity| {
+                let p = world.get::<Position>(entity).copied().unwrap_or_default();
+                (entity, point(p.x, p.y))
+            })
+            .collect();
+        world
+            .entity_mut(group.0)
+            .insert(AxesState { coords, members });
+
+        AxesId {
+            group,
+            x_axis,
+            y_axis,
+            ticks,
+            labels,
+            titles,
+        }
+    }
+}
+
+crate::stroke_builder!(AxesBuilder);
+crate::position_builder!(AxesBuilder);
+
+pub fn axes(scene: &mut Scene) -> AxesBuilder<'_> {
+    AxesBuilder::new(scene)
+}
+
+/// Pair of axes made of several objects, along with the mapping between
+/// data coordinates and scene points.
+///
+/// Objects placed in data coordinates, such as graphs created with
+/// [AxesId::function_graph] or objects given to [AxesId::add], follow the
+/// axes when they are moved or scaled. The mapping and these objects are
+/// stored in the scene, so methods reading or changing them take the scene.
+///
+/// [AxesId::scale] and [AxesId::move_to] update the mapping as soon as they
+/// are called, before the returned animations play. Coordinates read or
+/// objects added in between already use the new mapping.
+#[derive(Debug, Clone)]
+pub struct AxesId {
+    group: EmptyId,
+    x_axis: ArrowId,
+    y_axis: ArrowId,
+    ticks: Vec<LineId>,
+    labels: Vec<TextId>,
+    titles: Vec<TextId>,
+}
+
+impl AxesId {
+    /// [Empty](crate::Empty) object holding the parts of the axes as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn x_axis(&self) -> ArrowId {
+        self.x_axis
+    }
+    pub fn y_axis(&self) -> ArrowId {
+        self.y_axis
+    }
+    pub fn ticks(&self) -> &[LineId] {
+        &self.ticks
+    }
+    pub fn labels(&self) -> &[TextId] {
+        &self.labels
+    }
+    pub fn titles(&self) -> &[TextId] {
+        &self.titles
+    }
+    /// Current mapping between data coordinates and scene points, which
+    /// follows [AxesId::scale] and [AxesId::move_to].
+    pub fn coordinates(&self, scene: &Scene) -> Coordinates {
+        scene
+            .world
+            .borrow()
+            .get::<AxesState>(self.group.0)
+            .map(|state| state.coords.clone())
+            .expect("axes should be in the scene")
+    }
+    pub fn coords_to_point(&self, scene: &Scene, x: f32, y: f32) -> Point {
+        self.coordinates(scene).coords_to_point(x, y)
+    }
+    pub fn point_to_coords(&self, scene: &Scene, p: Point) -> (f32, f32) {
+        self.coordinates(scene).point_to_coords(p)
+    }
+    /// Graph of `y = function(x)` over the range of the axes, in data
+    /// coordinates. The graph moves along with the axes.
+    pub fn function_graph<'a>(
+        &self,
+        scene: &'a mut Scene,
+        function: impl Fn(f32) -> f32 + 'a,
+    ) -> crate::FunctionGraphBuilder<'a> {
+        let coords = self.coordinates(scene);
+        crate::function_graph(scene, function, coords.x_range)
+            .with_y_range(coords.y_range)
+            .on_axes(self)
+    }
+    fn state_mut<'a>(&self, scene: &'a mut Scene) -> Mut<'a, AxesState> {
+        scene
+            .world
+            .get_mut()
+            .get_mut::<AxesState>(self.group.0)
+            .expect("axes should be in the scene")
+    }
+    /// Make an object move along with the axes.
+    pub fn add(&self, scene: &mut Scene, object: impl Into<Entity>) {
+        let entity = object.into();
+        let p = scene
+            .world
+            .get_mut()
+            .get::<Position>(entity)
+            .copied()
+            .unwrap_or_default();
+        self.state_mut(scene)
+            .members
+            .push((entity, point(p.x, p.y)));
+    }
+    /// Scale the axes and all objects on them about the center of the axes.
+    ///
+    /// The mapping changes right away, while the returned animations move
+    /// the objects once they are played.
+    pub fn scale(&self, scene: &mut Scene, by: f32) -> Vec<EntityAnimations> {
+        let mut state = self.state_mut(scene);
+        let state = &mut *state;
+        let center = state.coords.center;
+        state.coords.x_length *= by;
+        state.coords.y_length *= by;
+        state
+            .members
+            .iter_mut()
+            .map(|(entity, p)| {
+                *p = center + (*p - center) * by;
+                EntityAnimations {
+                    entity: *entity,
+                    animations: vec![
+                        Animation::to(Position { x: p.x, y: p.y }).into(),
+                        Animation::times(Scale::new(by, by)).into(),
+                    ],
+                }
+            })
+            .collect()
+    }
+    /// Move the center of the axes along with all objects on them.
+    ///
+    /// The mapping changes right away, while the returned animations move
+    /// the objects once they are played.
+    pub fn move_to(&self, scene: &mut Scene, x: f32, y: f32) -> Vec<EntityAnimations> {
+        let mut state = self.state_mut(scene);
+        let state = &mut *state;
+        let shift = point(x, y) - state.coords.center;
+        state.coords.center = point(x, y);
+        state
+            .members
+            .iter_mut()
+            .map(|(entity, p)| {
+                *p += shift;
+                EntityAnimations {
+                    entity: *entity,
+                    animations: Animation::to(Position { x: p.x, y: p.y }).into(),
+                }
+            })
+            .collect()
+    }
+    /// Arrows, ticks, labels and titles of the axes.
+    fn parts(&self) -> impl Iterator<Item = Entity> + '_ {
+        [self.x_axis.0, self.y_axis.0]
+            .into_iter()
+            .chain(self.ticks.iter().map(|id| id.0))
+            .chain(self.labels.iter().chain(self.titles.iter()).map(|id| id.0))
+    }
+    /// Draw the parts of the axes. Objects given to [AxesId::add] are
+    /// animated on their own.
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::show_creation).collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::fade_in).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::fade_out).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinates_round_trip() {
+        let coords = Coordinates {
+            x_range: 0.0..10.0,
+            y_range: -1.0..1.0,
+            center: point(1.0, 0.5),
+            x_length: 5.0,
+            y_length: 2.0,
+        };
+        let p = coords.coords_to_point(0.0, 0.0);
+        assert_eq!((p.x, p.y), (-1.5, 0.5));
+        let p = coords.coords_to_point(10.0, 1.0);
+        assert_eq!((p.x, p.y), (3.5, 1.5));
+        let p = coords.to_scene().transform_point(point(10.0, 1.0));
+        assert_eq!((p.x, p.y), (3.5, 1.5));
+        let (x, y) = coords.point_to_coords(coords.coords_to_point(2.5, -0.25));
+        assert!((x - 2.5).abs() < 1.0e-5 && (y + 0.25).abs() < 1.0e-5);
+        assert_eq!(coords.origin(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn copies_of_axes_share_state() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let axes = axes(&mut scene).make();
+        let copy = axes.clone();
+        let graph = axes.function_graph(&mut scene, |x| x * x).make();
+        let animations = copy.move_to(&mut scene, 1.0, 0.0);
+        // The graph follows the axes even though it was added through the
+        // other copy.
+        assert!(animations.iter().any(|a| a.entity == graph.0));
+        let p = axes.coords_to_point(&scene, 0.0, 0.0);
+        assert_eq!((p.x, p.y), (1.0, 0.0));
+
+        axes.scale(&mut scene, 2.0);
+        let p = copy.coords_to_point(&scene, 1.0, 1.0);
+        assert_eq!((p.x, p.y), (3.0, 2.0));
+    }
+
+    #[test]
+    fn ticks_and_labels() {
+        assert_eq!(tick_values(&(-1.0..2.5), 1.0), vec![-1.0, 0.0, 1.0, 2.0]);
+        assert_eq!(tick_values(&(0.2..1.7), 0.5), vec![0.5, 1.0, 1.5]);
+        assert_eq!(format_number(2.0), "2");
+        assert_eq!(format_number(-0.5), "-0.5");
+        assert_eq!(format_number(0.30000001), "0.3");
+        assert_eq!(format_number(-0.001), "0");
+    }
+}
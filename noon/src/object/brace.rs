@@ -0,0 +1,375 @@
+use super::common::*;
+use crate::prelude::Direction;
+use crate::{TextId, Vector};
+
+/// Component indicating a curly brace.
+///
+/// The brace is laid out along the x axis and centered at the origin, with
+/// the length it spans given by the width of its [Size]. Its tip points
+/// towards negative y, `depth` below the ends.
+///
+/// When `target` is set, the brace follows the current [Size], [Position],
+/// [Angle] and [Scale] of the target object, staying on its `direction`
+/// side.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Brace {
+    pub target: Option<Entity>,
+    pub direction: Direction,
+    /// Gap between the target and the ends of the brace.
+    pub buff: f32,
+    pub depth: f32,
+    /// Text placed at the tip of the brace.
+    pub label: Option<Entity>,
+}
+
+impl Default for Brace {
+    fn default() -> Self {
+        Self {
+            target: None,
+            direction: Direction::Down,
+            buff: 0.1,
+            depth: 0.2,
+            label: None,
+        }
+    }
+}
+
+impl Brace {
+    /// Size, position and angle of the brace for a target with given
+    /// attributes.
+    pub(crate) fn layout(
+        &self,
+        size: &Size,
+        position: &Position,
+        angle: &Angle,
+        scale: &Scale,
+    ) -> (Size, Position, Angle) {
+        let width = size.width * scale.x;
+        let height = size.height * scale.y;
+        let (local, span, extent) = match self.direction {
+            Direction::Up => (Vector::new(0.0, 1.0), width, height),
+            Direction::Down => (Vector::new(0.0, -1.0), width, height),
+            Direction::Left => (Vector::new(-1.0, 0.0), height, width),
+            Direction::Right => (Vector::new(1.0, 0.0), height, width),
+        };
+        let (sin, cos) = angle.0.sin_cos();
+        let tip = Vector::new(local.x * cos - local.y * sin, local.x * sin + local.y * cos);
+        let offset = tip * (extent / 2.0 + self.buff + self.depth / 2.0);
+        (
+            Size::from(span, self.depth),
+            Position {
+                x: position.x + offset.x,
+                y: position.y + offset.y,
+            },
+            Angle(tip.y.atan2(tip.x) + std::f32::consts::FRAC_PI_2),
+        )
+    }
+
+    /// Point of the tip, given the position and angle of the brace.
+    pub(crate) fn tip(&self, position: &Position, angle: &Angle) -> (Point, Vector) {
+        let direction = Vector::new(angle.0.sin(), -angle.0.cos());
+        (
+            point(position.x, position.y) + direction * self.depth / 2.0,
+            direction,
+        )
+    }
+}
+
+impl PathComponent for Brace {
+    fn path(&self, size: &Size) -> Path {
+        let w = size.width.max(0.0) / 2.0;
+        let h = size.height.min(w).max(0.0) / 2.0;
+        let mut builder = Path::svg_builder();
+        builder.move_to(point(-w, h));
+        builder.quadratic_bezier_to(point(-w, 0.0), point(-w + h, 0.0));
+        builder.line_to(point(-h, 0.0));
+        builder.quadratic_bezier_to(point(0.0, 0.0), point(0.0, -h));
+        builder.quadratic_bezier_to(point(0.0, 0.0), point(h, 0.0));
+        builder.line_to(point(w - h, 0.0));
+        builder.quadratic_bezier_to(point(w, 0.0), point(w, h));
+        Path::new(builder.build(), false)
+    }
+}
+
+/// Position of a label with given size, placed at `tip` beyond a brace
+/// pointing towards `direction`.
+pub(crate) fn label_position(tip: Point, direction: Vector, size: &Size) -> Position {
+    let gap = 0.1;
+    Position {
+        x: tip.x + direction.x * (gap + size.width / 2.0),
+        y: tip.y + direction.y * (gap + size.height / 2.0),
+    }
+}
+
+pub struct BraceBuilder<'a> {
+    brace: Brace,
+    /// End points when not attached to a target.
+    points: (Point, Point),
+    label: Option<String>,
+    font_size: f32,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    scene: &'a mut Scene,
+}
+
+impl<'a> BraceBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        Self {
+            brace: Brace::default(),
+            points: (point(-1.0, 0.0), point(1.0, 0.0)),
+            label: None,
+            font_size: 0.3,
+            stroke_weight: StrokeWeight::THIN,
+            stroke_color: Color::WHITE,
+            scene,
+        }
+    }
+    /// Put a text at the tip of the brace.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    /// Gap between the target and the ends of the brace.
+    pub fn with_buff(mut self, buff: f32) -> Self {
+        self.brace.buff = buff;
+        self
+    }
+    /// Distance from the ends of the brace to its tip.
+    pub fn with_depth(mut self, depth: f32) -> Self {
+        self.brace.depth = depth;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+
+    /// Size, position and angle of the brace from the current state of its
+    /// target, or from its end points.
+    fn layout(&self) -> (Size, Position, Angle) {
+        let world = self.scene.world.borrow();
+        let target = self.brace.target.and_then(|entity| {
+            Some((
+                world.get::<Size>(entity)?,
+                world.get::<Position>(entity)?,
+                world.get::<Angle>(entity).copied().unwrap_or_default(),
+                world.get::<Scale>(entity).copied().unwrap_or(Scale::ONE),
+            ))
+        });
+        match target {
+            Some((size, position, angle, scale)) => {
+                self.brace.layout(size, position, &angle, &scale)
+            }
+            None => {
+                let (from, to) = self.points;
+                let delta = to - from;
+                let normal = Vector::new(delta.y, -delta.x).normalize();
+                let center = from.lerp(to, 0.5) + normal * self.brace.depth / 2.0;
+                (
+                    Size::from(delta.length(), self.brace.depth),
+                    Position {
+                        x: center.x,
+                        y: center.y,
+                    },
+                    Angle(delta.y.atan2(delta.x)),
+                )
+            }
+        }
+    }
+}
+
+crate::stroke_builder!(BraceBuilder);
+
+impl Create<BraceId> for BraceBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> BraceId {
+        let (size, position, angle) = self.layout();
+
+        let label = self.label.clone().map(|label| {
+            let id = crate::text(self.scene)
+                .with_text(&label)
+                .with_font_size(self.font_size)
+                .with_color(self.stroke_color)
+                .make();
+            let world = self.scene.world.get_mut();
+            let (tip, direction) = self.brace.tip(&position, &angle);
+            if let Some(label_size) = world.get::<Size>(id.0).copied() {
+                if let Some(mut p) = world.get_mut::<Position>(id.0) {
+                    *p = label_position(tip, direction, &label_size);
+                }
+            }
+            id
+        });
+        self.brace.label = label.map(|l| l.0);
+
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let path = self.brace.path(&size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(angle)
+            .translate(position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(self.brace)
+            .insert(size)
+            .insert(BoundingSize(size))
+            .insert(scale)
+            .insert(position)
+            .insert(angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.stroke_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(false))
+            .id();
+
+        id.into()
+    }
+}
+
+/// Brace next to the given object, on the side given by `direction`.
+pub fn brace_under(
+    scene: &mut Scene,
+    target: impl Into<Entity>,
+    direction: Direction,
+) -> BraceBuilder<'_> {
+    let mut builder = BraceBuilder::new(scene);
+    builder.brace.target = Some(target.into());
+    builder.brace.direction = direction;
+    builder
+}
+
+/// Brace spanning from `from` to `to`, with its tip on the right-hand side
+/// when looking from `from` towards `to`.
+pub fn brace_between(scene: &mut Scene, from: Point, to: Point) -> BraceBuilder<'_> {
+    let mut builder = BraceBuilder::new(scene);
+    builder.points = (from, to);
+    builder
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct BraceId(pub(crate) Entity);
+crate::into_entity!(BraceId);
+
+impl BraceId {
+    /// Text at the tip of the brace, if made [with_label](BraceBuilder::with_label).
+    pub fn label(&self, scene: &Scene) -> Option<TextId> {
+        let world = scene.world.borrow();
+        world.get::<Brace>(self.0)?.label.map(TextId)
+    }
+}
+
+impl WithStroke for BraceId {}
+impl WithColor for BraceId {}
+impl WithPath for BraceId {}
+impl WithPosition for BraceId {}
+impl WithAngle for BraceId {}
+impl WithStrokeWeight for BraceId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brace_sits_beside_target() {
+        let brace = Brace::default();
+        let size = Size::from(2.0, 1.0);
+        let position = Position { x: 1.0, y: 0.0 };
+        let (s, p, a) = brace.layout(&size, &position, &Angle(0.0), &Scale::ONE);
+        assert_eq!((s.width, s.height), (2.0, 0.2));
+        assert!((p.x - 1.0).abs() < EPS && (p.y + 0.7).abs() < EPS);
+        assert!(a.0.abs() < EPS);
+        let (tip, _) = brace.tip(&p, &a);
+        assert!((tip.y + 0.8).abs() < EPS);
+
+        // Rotating the target by a quarter turn puts the brace on its right.
+        let brace = Brace {
+            direction: Direction::Right,
+            ..Default::default()
+        };
+        let angle = Angle(std::f32::consts::FRAC_PI_2);
+        let (s, p, _) = brace.layout(&size, &position, &angle, &Scale::ONE);
+        assert_eq!(s.width, 1.0);
+        assert!((p.x - 1.0).abs() < EPS && (p.y - 1.2).abs() < EPS);
+
+        let path = brace.path(&Size::from(2.0, 0.2)).size();
+        assert!((path.width - 2.0).abs() < EPS && (path.height - 0.2).abs() < EPS);
+    }
+
+    #[test]
+    fn brace_keeps_own_animations_while_target_is_still() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let rect = crate::rectangle(&mut scene).with_size(2.0, 1.0).make();
+        let brace = brace_under(&mut scene, rect, Direction::Down)
+            .with_label("a")
+            .make();
+        let label = brace.label(&scene).unwrap();
+        let rect_rect = nannou::geom::Rect::from_w_h(640.0, 480.0);
+        scene.update(0.0, rect_rect);
+
+        scene.play(brace.move_to(3.0, 3.0)).run_time(1.0);
+        scene.play(label.move_to(-3.0, 0.0)).run_time(1.0);
+        for i in 0..=20 {
+            scene.update(0.5 + i as f32 * 0.1, rect_rect);
+        }
+        let world = scene.world.get_mut();
+        let p = world.get::<Position>(brace.0).unwrap();
+        assert!((p.x - 3.0).abs() < EPS && (p.y - 3.0).abs() < EPS);
+        let p = world.get::<Position>(label.0).unwrap();
+        assert!((p.x + 3.0).abs() < EPS && p.y.abs() < EPS);
+
+        // Moving the target lays the brace out next to it again.
+        scene.play(rect.move_to(1.0, 0.0)).run_time(1.0);
+        for i in 0..=20 {
+            scene.update(2.5 + i as f32 * 0.1, rect_rect);
+        }
+        let p = scene
+            .world
+            .get_mut()
+            .get::<Position>(brace.0)
+            .copied()
+            .unwrap();
+        assert!((p.x - 1.0).abs() < EPS && (p.y + 0.7).abs() < EPS);
+    }
+
+    #[test]
+    fn brace_follows_group_target() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let group = crate::empty(&mut scene).with_size(2.0, 1.0).make();
+        let brace = brace_under(&mut scene, group, Direction::Down).make();
+        let rect = nannou::geom::Rect::from_w_h(640.0, 480.0);
+        scene.update(0.0, rect);
+
+        scene.play(group.move_to(1.0, 1.0)).run_time(1.0);
+        for i in 0..=20 {
+            scene.update(0.5 + i as f32 * 0.1, rect);
+        }
+        let p = scene
+            .world
+            .get_mut()
+            .get::<Position>(brace.0)
+            .copied()
+            .unwrap();
+        assert!((p.x - 1.0).abs() < EPS && (p.y - 0.3).abs() < EPS);
+    }
+}
@@ -8,9 +8,9 @@ use nannou::lyon::math::Vector;
 #[derive(Component)]
 pub struct Circle;
 
-impl Circle {
-    /// Returns path for a circle.
-    fn path(size: &Size) -> Path {
+impl PathComponent for Circle {
+    /// Returns path for a circle, using the width of `size` as its diameter.
+    fn path(&self, size: &Size) -> Path {
         let radius = size.width / 2.0;
         let mut builder = Path::svg_builder();
         let sweep_angle = nannou::lyon::math::Angle::radians(TAU);
@@ -75,7 +75,8 @@ impl Create<CircleId> for CircleBuilder<'_> {
         let world = self.scene.world.get_mut();
         let position = self.position;
         let scale = Scale::ONE;
-        let path = Circle::path(&Size::from_radius(self.radius));
+        let size = Size::from_radius(self.radius);
+        let path = Circle.path(&size);
         let transform = Transform::identity()
             .scale(scale)
             .rotate(self.angle)
@@ -90,7 +91,8 @@ impl Create<CircleId> for CircleBuilder<'_> {
         let id = world
             .spawn_empty()
             .insert(Circle)
-            .insert(Size::from_radius(self.radius))
+            .insert(size)
+            .insert(BoundingSize(size))
             .insert(scale)
             .insert(self.position)
             .insert(self.angle)
@@ -0,0 +1,521 @@
+use std::ops::Range;
+
+/// Category of a token of source code, used to pick its color from a
+/// [Theme](super::Theme).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Keyword,
+    /// Type names, i.e. primitive types and capitalized identifiers.
+    Type,
+    /// Names of defined functions, and identifiers directly followed by an
+    /// opening parenthesis.
+    Function,
+    /// Rust macro invocations, including the `!`.
+    Macro,
+    String,
+    Number,
+    Comment,
+    /// Rust attributes, decorators and C preprocessor directives.
+    Attribute,
+    Lifetime,
+    Operator,
+    Punctuation,
+    /// Any other identifier, or any text in an unknown language.
+    Plain,
+    Whitespace,
+}
+
+/// How a language marks attributes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Attributes {
+    None,
+    /// `#[...]` and `#![...]`.
+    Bracketed,
+    /// `@name`.
+    Decorator,
+    /// `#` up to the end of the line.
+    Directive,
+}
+
+/// Lexical rules of a language, just detailed enough for highlighting.
+pub(crate) struct Language {
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    line_comment: &'static str,
+    block_comment: Option<(&'static str, &'static str)>,
+    attributes: Attributes,
+    /// Whether `'a` is a lifetime rather than the start of a string.
+    lifetimes: bool,
+    /// Whether `name!` is a macro invocation.
+    macros: bool,
+}
+
+const RUST: Language = Language {
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true",
+        "type", "unsafe", "use", "where", "while",
+    ],
+    types: &[
+        "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+        "i64", "i128", "isize", "f32", "f64",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    attributes: Attributes::Bracketed,
+    lifetimes: true,
+    macros: true,
+};
+
+const PYTHON: Language = Language {
+    keywords: &[
+        "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+        "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+        "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+        "self", "try", "while", "with", "yield",
+    ],
+    types: &[
+        "bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple",
+    ],
+    line_comment: "#",
+    block_comment: None,
+    attributes: Attributes::Decorator,
+    lifetimes: false,
+    macros: false,
+};
+
+const JAVASCRIPT: Language = Language {
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "of",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    types: &["any", "boolean", "never", "number", "string", "unknown"],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    attributes: Attributes::Decorator,
+    lifetimes: false,
+    macros: false,
+};
+
+const C: Language = Language {
+    keywords: &[
+        "auto",
+        "break",
+        "case",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "extern",
+        "false",
+        "for",
+        "goto",
+        "if",
+        "namespace",
+        "new",
+        "nullptr",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "true",
+        "typedef",
+        "union",
+        "using",
+        "virtual",
+        "volatile",
+        "while",
+    ],
+    types: &[
+        "bool", "char", "double", "float", "int", "long", "short", "signed", "size_t", "unsigned",
+        "void",
+    ],
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    attributes: Attributes::Directive,
+    lifetimes: false,
+    macros: false,
+};
+
+impl Language {
+    /// Rules for the language with the given name or file extension, if
+    /// known.
+    pub(crate) fn get(name: &str) -> Option<&'static Language> {
+        match name.to_ascii_lowercase().as_str() {
+            "rust" | "rs" => Some(&RUST),
+            "python" | "py" => Some(&PYTHON),
+            "javascript" | "js" | "typescript" | "ts" => Some(&JAVASCRIPT),
+            "c" | "h" | "cpp" | "c++" | "hpp" | "cc" => Some(&C),
+            _ => None,
+        }
+    }
+}
+
+/// Split `source` into consecutive tokens covering all of it.
+///
+/// Without a language, words and whitespace are told apart but nothing
+/// gets highlighted.
+pub(crate) fn tokenize(
+    source: &str,
+    language: Option<&Language>,
+) -> Vec<(TokenKind, Range<usize>)> {
+    let mut lexer = Lexer {
+        source,
+        position: 0,
+        language,
+        definition: false,
+    };
+    let mut tokens = Vec::new();
+    while lexer.position < source.len() {
+        let start = lexer.position;
+        let kind = lexer.token();
+        if kind != TokenKind::Whitespace && kind != TokenKind::Keyword {
+            lexer.definition = false;
+        }
+        tokens.push((kind, start..lexer.position));
+    }
+    tokens
+}
+
+struct Lexer<'a> {
+    source: &'a str,
+    position: usize,
+    language: Option<&'a Language>,
+    /// Whether the last keyword introduces the name of a function.
+    definition: bool,
+}
+
+impl Lexer<'_> {
+    fn rest(&self) -> &str {
+        &self.source[self.position..]
+    }
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+    fn peek_second(&self) -> Option<char> {
+        self.rest().chars().nth(1)
+    }
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.position += ch.len_utf8();
+        Some(ch)
+    }
+    fn eat_while(&mut self, f: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&f) {
+            self.bump();
+        }
+    }
+    fn eat_line(&mut self) {
+        self.eat_while(|c| c != '\n');
+    }
+    fn eat_until(&mut self, end: &str) {
+        match self.rest().find(end) {
+            Some(i) => self.position += i + end.len(),
+            None => self.position = self.source.len(),
+        }
+    }
+
+    fn token(&mut self) -> TokenKind {
+        let ch = self.peek().unwrap_or_default();
+        if ch.is_whitespace() {
+            self.eat_while(char::is_whitespace);
+            return TokenKind::Whitespace;
+        }
+        let Some(language) = self.language else {
+            self.eat_while(|c| !c.is_whitespace());
+            return TokenKind::Plain;
+        };
+
+        if self.rest().starts_with(language.line_comment) {
+            self.eat_line();
+            return TokenKind::Comment;
+        }
+        if let Some((open, close)) = language.block_comment {
+            if self.rest().starts_with(open) {
+                self.position += open.len();
+                self.eat_until(close);
+                return TokenKind::Comment;
+            }
+        }
+        match (language.attributes, ch) {
+            (Attributes::Bracketed, '#') if matches!(self.peek_second(), Some('[' | '!')) => {
+                self.bracketed();
+                return TokenKind::Attribute;
+            }
+            (Attributes::Decorator, '@') => {
+                self.bump();
+                self.eat_while(|c| c.is_alphanumeric() || c == '_' || c == '.');
+                return TokenKind::Attribute;
+            }
+            (Attributes::Directive, '#') => {
+                self.eat_line();
+                return TokenKind::Attribute;
+            }
+            _ => {}
+        }
+
+        if ch == '\'' && language.lifetimes {
+            return self.quote();
+        }
+        if ch == '"' || ch == '\'' || ch == '`' {
+            self.bump();
+            self.string(ch);
+            return TokenKind::String;
+        }
+        if language.lifetimes && self.raw_string() {
+            return TokenKind::String;
+        }
+        if ch.is_ascii_digit() {
+            self.number();
+            return TokenKind::Number;
+        }
+        if ch.is_alphabetic() || ch == '_' {
+            return self.word(language);
+        }
+        if "+-*/%=<>!&|^~?:".contains(ch) {
+            self.eat_while(|c| "+-*/%=<>!&|^~?:".contains(c));
+            return TokenKind::Operator;
+        }
+        self.bump();
+        TokenKind::Punctuation
+    }
+
+    /// `#[...]`, up to the matching closing bracket.
+    fn bracketed(&mut self) {
+        let mut depth = 0;
+        while let Some(ch) = self.bump() {
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                '\n' => break,
+                _ => {}
+            }
+        }
+    }
+
+    /// Rest of a string literal opened by `quote`, handling escapes.
+    fn string(&mut self, quote: char) {
+        while let Some(ch) = self.bump() {
+            if ch == '\\' {
+                self.bump();
+            } else if ch == quote {
+                break;
+            }
+        }
+    }
+
+    /// Rust raw and byte strings such as `r#"..."#` or `b"..."`.
+    fn raw_string(&mut self) -> bool {
+        let rest = self.rest();
+        let prefix = if rest.starts_with("br") {
+            2
+        } else if rest.starts_with('r') || rest.starts_with('b') {
+            1
+        } else {
+            return false;
+        };
+        let hashes = rest[prefix..].chars().take_while(|&c| c == '#').count();
+        if !rest[prefix + hashes..].starts_with('"') {
+            return false;
+        }
+        let raw = rest[..prefix].contains('r');
+        if !raw && hashes > 0 {
+            return false;
+        }
+        self.position += prefix + hashes + 1;
+        if raw {
+            self.eat_until(&format!("\"{}", "#".repeat(hashes)));
+        } else {
+            self.string('"');
+        }
+        true
+    }
+
+    /// A character literal or a lifetime in Rust.
+    fn quote(&mut self) -> TokenKind {
+        self.bump();
+        let rest = self.rest();
+        let mut chars = rest.chars();
+        let is_char = matches!(
+            (chars.next(), chars.next()),
+            (Some('\\'), _) | (Some(_), Some('\''))
+        );
+        if is_char {
+            self.string('\'');
+            TokenKind::String
+        } else {
+            self.eat_while(|c| c.is_alphanumeric() || c == '_');
+            TokenKind::Lifetime
+        }
+    }
+
+    fn number(&mut self) {
+        loop {
+            self.eat_while(|c| c.is_alphanumeric() || c == '_');
+            // Keep decimals but not ranges such as `0..10` or method calls.
+            if self.peek() == Some('.') && self.peek_second().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn word(&mut self, language: &Language) -> TokenKind {
+        let start = self.position;
+        self.eat_while(|c| c.is_alphanumeric() || c == '_');
+        let word = &self.source[start..self.position];
+        if language.keywords.contains(&word) {
+            self.definition = matches!(word, "fn" | "def" | "function");
+            return TokenKind::Keyword;
+        }
+        if language.macros && self.peek() == Some('!') && self.peek_second() != Some('=') {
+            self.bump();
+            return TokenKind::Macro;
+        }
+        if language.types.contains(&word) || word.starts_with(char::is_uppercase) {
+            return TokenKind::Type;
+        }
+        if self.definition || self.peek() == Some('(') || self.rest().starts_with("::<") {
+            return TokenKind::Function;
+        }
+        TokenKind::Plain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds<'a>(source: &'a str, language: &str) -> Vec<(TokenKind, &'a str)> {
+        tokenize(source, Language::get(language))
+            .into_iter()
+            .filter(|(kind, _)| *kind != TokenKind::Whitespace)
+            .map(|(kind, range)| (kind, &source[range]))
+            .collect()
+    }
+
+    #[test]
+    fn rust_tokens() {
+        use TokenKind::*;
+        let tokens = kinds(
+            "#[derive(Debug)]\nfn first<'a>(x: &'a str) -> char { println!(\"{x}\"); 'x' } // done",
+            "rust",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                (Attribute, "#[derive(Debug)]"),
+                (Keyword, "fn"),
+                (Function, "first"),
+                (Operator, "<"),
+                (Lifetime, "'a"),
+                (Operator, ">"),
+                (Punctuation, "("),
+                (Plain, "x"),
+                (Operator, ":"),
+                (Operator, "&"),
+                (Lifetime, "'a"),
+                (Type, "str"),
+                (Punctuation, ")"),
+                (Operator, "->"),
+                (Type, "char"),
+                (Punctuation, "{"),
+                (Macro, "println!"),
+                (Punctuation, "("),
+                (String, "\"{x}\""),
+                (Punctuation, ")"),
+                (Punctuation, ";"),
+                (String, "'x'"),
+                (Punctuation, "}"),
+                (Comment, "// done"),
+            ]
+        );
+        assert_eq!(
+            kinds("0..10 1.5e3 r#\"a\"b\"#", "rs"),
+            vec![
+                (Number, "0"),
+                (Punctuation, "."),
+                (Punctuation, "."),
+                (Number, "10"),
+                (Number, "1.5e3"),
+                (String, "r#\"a\"b\"#"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens_cover_source() {
+        let source = "def f(x):\n    return 'a' # comment\n";
+        let tokens = tokenize(source, Language::get("python"));
+        let joined: std::string::String = tokens.iter().map(|(_, r)| &source[r.clone()]).collect();
+        assert_eq!(joined, source);
+        assert_eq!(
+            kinds("/* a\nb */ x", "unknown"),
+            vec![
+                (TokenKind::Plain, "/*"),
+                (TokenKind::Plain, "a"),
+                (TokenKind::Plain, "b"),
+                (TokenKind::Plain, "*/"),
+                (TokenKind::Plain, "x"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,560 @@
+mod highlight;
+
+pub use highlight::TokenKind;
+
+use super::common::*;
+use super::path::insert_path;
+use crate::color::rgb_from_hex;
+use crate::{EmptyId, FontFamily, FontId, Fonts, PathId, RectangleId};
+use highlight::{tokenize, Language};
+use nannou::lyon::algorithms::aabb::bounding_rect;
+use nannou::lyon::geom::Translation;
+use nannou::lyon::math::Transform as LyonTransform;
+use nannou::text::{glyph, GlyphId};
+
+/// Colors of a code block.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub background: Color,
+    /// Band behind highlighted lines.
+    pub highlight: Color,
+    pub line_number: Color,
+    pub plain: Color,
+    pub keyword: Color,
+    pub type_name: Color,
+    pub function: Color,
+    pub macro_call: Color,
+    pub string: Color,
+    pub number: Color,
+    pub comment: Color,
+    pub attribute: Color,
+    pub lifetime: Color,
+    pub operator: Color,
+    pub punctuation: Color,
+}
+
+impl Theme {
+    /// Colors after the One Dark theme.
+    pub fn dark() -> Self {
+        Self {
+            background: rgb_from_hex(0x282c34),
+            highlight: rgb_from_hex(0x3e4451),
+            line_number: rgb_from_hex(0x636d83),
+            plain: rgb_from_hex(0xabb2bf),
+            keyword: rgb_from_hex(0xc678dd),
+            type_name: rgb_from_hex(0xe5c07b),
+            function: rgb_from_hex(0x61afef),
+            macro_call: rgb_from_hex(0x56b6c2),
+            string: rgb_from_hex(0x98c379),
+            number: rgb_from_hex(0xd19a66),
+            comment: rgb_from_hex(0x7f848e),
+            attribute: rgb_from_hex(0xe06c75),
+            lifetime: rgb_from_hex(0xd19a66),
+            operator: rgb_from_hex(0x56b6c2),
+            punctuation: rgb_from_hex(0xabb2bf),
+        }
+    }
+    /// Colors after the One Light theme.
+    pub fn light() -> Self {
+        Self {
+            background: rgb_from_hex(0xfafafa),
+            highlight: rgb_from_hex(0xe5e5e6),
+            line_number: rgb_from_hex(0x9d9d9f),
+            plain: rgb_from_hex(0x383a42),
+            keyword: rgb_from_hex(0xa626a4),
+            type_name: rgb_from_hex(0xc18401),
+            function: rgb_from_hex(0x4078f2),
+            macro_call: rgb_from_hex(0x0184bc),
+            string: rgb_from_hex(0x50a14f),
+            number: rgb_from_hex(0x986801),
+            comment: rgb_from_hex(0xa0a1a7),
+            attribute: rgb_from_hex(0xe45649),
+            lifetime: rgb_from_hex(0x986801),
+            operator: rgb_from_hex(0x0184bc),
+            punctuation: rgb_from_hex(0x383a42),
+        }
+    }
+    pub fn color(&self, kind: TokenKind) -> Color {
+        match kind {
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_name,
+            TokenKind::Function => self.function,
+            TokenKind::Macro => self.macro_call,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Comment => self.comment,
+            TokenKind::Attribute => self.attribute,
+            TokenKind::Lifetime => self.lifetime,
+            TokenKind::Operator => self.operator,
+            TokenKind::Punctuation => self.punctuation,
+            TokenKind::Plain | TokenKind::Whitespace => self.plain,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Column of each character of `line`, followed by the column just past
+/// its end. Tabs advance to the next multiple of `tab_width`.
+fn columns(line: &str, tab_width: usize) -> Vec<usize> {
+    let tab_width = tab_width.max(1);
+    let mut columns = vec![0];
+    for ch in line.chars() {
+        let column = *columns.last().unwrap();
+        columns.push(match ch {
+            '\t' => (column / tab_width + 1) * tab_width,
+            _ => column + 1,
+        });
+    }
+    columns
+}
+
+/// Builder for a block of source code on a background panel.
+///
+/// Every character takes the same width, so code lines up even with a
+/// proportional font: glyphs wider than a cell are narrowed to fit. The
+/// width of a cell is the advance of `0` in the first font of the block.
+///
+/// Tokens are colored according to the [Theme] for the given language.
+/// Known languages are `rust`, `python`, `javascript`/`typescript` and
+/// `c`/`cpp`, also by their file extension. Any other language is shown
+/// without highlighting.
+pub struct CodeBuilder<'a> {
+    source: String,
+    language: Option<&'static Language>,
+    theme: Theme,
+    font_size: f32,
+    family: FontFamily,
+    line_numbers: bool,
+    first_line: usize,
+    tab_width: usize,
+    padding: f32,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> CodeBuilder<'a> {
+    fn new(scene: &'a mut Scene, source: &str, language: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            language: Language::get(language),
+            theme: Theme::default(),
+            font_size: 0.3,
+            family: Default::default(),
+            line_numbers: true,
+            first_line: 1,
+            tab_width: 4,
+            padding: 0.2,
+            position: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// Height of a line of code in scene units.
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    /// Use a font registered in the scene's [Fonts], ideally a monospaced one.
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.family = FontFamily::new(font);
+        self
+    }
+    /// Font to use for characters missing from the previously added fonts.
+    pub fn with_fallback_font(mut self, font: FontId) -> Self {
+        self.family.add(font);
+        self
+    }
+    /// Hide the line numbers.
+    pub fn without_line_numbers(mut self) -> Self {
+        self.line_numbers = false;
+        self
+    }
+    /// Number of the first line, e.g. when showing an excerpt of a file.
+    pub fn with_first_line(mut self, number: usize) -> Self {
+        self.first_line = number;
+        self
+    }
+    pub fn with_tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+    /// Space between the code and the edges of the background.
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Create the background, one object per token and line number, and a
+    /// hidden band behind every line used by [CodeId::highlight]. All of them
+    /// are grouped under an [Empty](crate::Empty) object centered on the
+    /// block.
+    pub fn make(&mut self) -> CodeId {
+        let fonts = self
+            .scene
+            .world
+            .get_mut()
+            .resource::<Fonts>()
+            .chain(&self.family);
+        let scale = nannou::text::Scale::uniform(self.font_size);
+        let v_metrics = fonts[0].v_metrics(scale);
+        let line_height = v_metrics.ascent - v_metrics.descent + v_metrics.line_gap;
+        let cell = fonts[0].glyph('0').scaled(scale).h_metrics().advance_width;
+
+        let source = self.source.strip_suffix('\n').unwrap_or(&self.source);
+        let mut starts = vec![0];
+        starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        let lines: Vec<_> = starts
+            .iter()
+            .map(|&start| {
+                let end = source[start..]
+                    .find('\n')
+                    .map_or(source.len(), |i| start + i);
+                let text = source[start..end].trim_end_matches('\r');
+                (start, text, columns(text, self.tab_width))
+            })
+            .collect();
+
+        let digits = (self.first_line + lines.len())
+            .saturating_sub(1)
+            .to_string()
+            .len();
+        let gutter = if self.line_numbers { digits + 2 } else { 0 };
+        let width = cell
+            * (gutter
+                + lines
+                    .iter()
+                    .map(|(_, _, c)| *c.last().unwrap())
+                    .max()
+                    .unwrap_or(0)) as f32;
+        let height = line_height * lines.len() as f32;
+        let left = self.position.x - width / 2.0;
+        let top = self.position.y + height / 2.0;
+
+        let background = crate::rectangle(self.scene)
+            .with_size(width + 2.0 * self.padding, height + 2.0 * self.padding)
+            .with_position(self.position.x, self.position.y)
+            .with_color(self.theme.background)
+            .with_stroke_weight(0.0)
+            .make();
+        let bands: Vec<RectangleId> = (0..lines.len())
+            .map(|row| {
+                crate::rectangle(self.scene)
+                    .with_size(width + 2.0 * self.padding, line_height)
+                    .with_position(self.position.x, top - (row as f32 + 0.5) * line_height)
+                    .with_color(self.theme.highlight)
+                    .with_stroke_weight(0.0)
+                    .make()
+            })
+            .collect();
+
+        // Path of the given characters, each centered in the cell of its column.
+        let cells = |chars: &mut dyn Iterator<Item = (usize, char)>, row: usize| {
+            let baseline = top - row as f32 * line_height - v_metrics.ascent;
+            let mut builder = Path::builder();
+            for (column, ch) in chars {
+                let font = fonts
+                    .iter()
+                    .position(|f| f.glyph(ch).id() != GlyphId(0))
+                    .unwrap_or(0);
+                let glyph = fonts[font].glyph(ch).scaled(scale);
+                let advance = glyph.h_metrics().advance_width;
+                let squeeze = if advance > cell { cell / advance } else { 1.0 };
+                let x = left + column as f32 * cell + (cell - advance * squeeze) / 2.0;
+                let transform =
+                    LyonTransform::scale(squeeze, 1.0).then_translate((x, baseline).into());
+                for event in glyph::path_events(glyph).into_iter().flatten() {
+                    builder.path_event(event.transformed(&transform));
+                }
+            }
+            builder.build()
+        };
+        let insert = |scene: &mut Scene, raw: nannou::lyon::path::Path, color: Color| {
+            raw.iter().next()?;
+            let center = bounding_rect(raw.iter()).center();
+            Some(insert_path(
+                scene,
+                Path::new(
+                    raw.transformed(&Translation::new(-center.x, -center.y)),
+                    true,
+                ),
+                Position {
+                    x: center.x,
+                    y: center.y,
+                },
+                Angle::default(),
+                StrokeWeight::THIN,
+                color,
+                Some(color),
+            ))
+        };
+
+        let tokens = tokenize(source, self.language);
+        let mut code_lines = Vec::with_capacity(lines.len());
+        for (row, ((start, text, columns), band)) in lines.iter().zip(bands).enumerate() {
+            let number = if self.line_numbers {
+                let label = (self.first_line + row).to_string();
+                let offset = digits - label.len();
+                let raw = cells(
+                    &mut label.chars().enumerate().map(|(i, c)| (offset + i, c)),
+                    row,
+                );
+                insert(self.scene, raw, self.theme.line_number)
+            } else {
+                None
+            };
+
+            let end = start + text.len();
+            let mut line_tokens = Vec::new();
+            for (kind, range) in tokens.iter() {
+                let (from, to) = (range.start.max(*start), range.end.min(end));
+                if from >= to || *kind == TokenKind::Whitespace {
+                    continue;
+                }
+                let first = text[..from - start].chars().count();
+                let mut chars = text[from - start..to - start]
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| !c.is_whitespace())
+                    .map(|(i, c)| (gutter + columns[first + i], c));
+                let raw = cells(&mut chars, row);
+                if let Some(id) = insert(self.scene, raw, self.theme.color(*kind)) {
+                    line_tokens.push((*kind, id));
+                }
+            }
+            code_lines.push(CodeLineId {
+                text: text.to_string(),
+                number,
+                tokens: line_tokens,
+                band,
+            });
+        }
+
+        let mut group = crate::empty(self.scene)
+            .with_position(self.position.x, self.position.y)
+            .with_size(width + 2.0 * self.padding, height + 2.0 * self.padding)
+            .add(background);
+        for line in code_lines.iter() {
+            group = group.add(line.band);
+            for entity in line.entities() {
+                group = group.add(entity);
+            }
+        }
+        CodeId {
+            group: group.make(),
+            background,
+            lines: code_lines,
+        }
+    }
+}
+
+crate::position_builder!(CodeBuilder);
+
+/// Block of `source` code, highlighted as `language`.
+pub fn code<'a>(scene: &'a mut Scene, source: &str, language: &str) -> CodeBuilder<'a> {
+    CodeBuilder::new(scene, source, language)
+}
+
+/// Line of a [CodeId], made of one object per token.
+#[derive(Debug, Clone)]
+pub struct CodeLineId {
+    text: String,
+    number: Option<PathId>,
+    tokens: Vec<(TokenKind, PathId)>,
+    band: RectangleId,
+}
+
+impl CodeLineId {
+    /// Source of the line, without the line break.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+    /// Line number in the gutter, unless made
+    /// [without_line_numbers](CodeBuilder::without_line_numbers).
+    pub fn number(&self) -> Option<PathId> {
+        self.number
+    }
+    /// Objects of the tokens of the line from left to right, without
+    /// whitespace.
+    pub fn tokens(&self) -> Vec<PathId> {
+        self.tokens.iter().map(|(_, id)| *id).collect()
+    }
+    /// Tokens of the given kind, e.g. every keyword of the line.
+    pub fn tokens_of(&self, kind: TokenKind) -> Vec<PathId> {
+        self.tokens
+            .iter()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, id)| *id)
+            .collect()
+    }
+    /// Band behind the line shown by [CodeId::highlight].
+    pub fn band(&self) -> RectangleId {
+        self.band
+    }
+
+    /// Morph the tokens of this line into those of `other` in order.
+    ///
+    /// Extra tokens of this line morph into the last token of `other`, and
+    /// extra tokens of `other` fade in.
+    pub fn morph(&self, other: &CodeLineId) -> Vec<EntityAnimations> {
+        let mut animations = Vec::new();
+        if let (Some(a), Some(b)) = (self.number, other.number) {
+            animations.push(a.morph(b));
+        }
+        let targets = other.tokens();
+        for (i, (_, token)) in self.tokens.iter().enumerate() {
+            match targets.get(i).or(targets.last()) {
+                Some(target) => animations.push(token.morph(*target)),
+                None => animations.push(token.fade_out()),
+            }
+        }
+        for target in targets.iter().skip(self.tokens.len()) {
+            animations.push(target.fade_in());
+        }
+        animations
+    }
+    pub fn set_color(&self, color: Color) -> Vec<EntityAnimations> {
+        self.tokens
+            .iter()
+            .map(|(_, id)| id.set_color(color))
+            .collect()
+    }
+    /// Animate the opacity of the line number and tokens.
+    pub fn set_opacity(&self, opacity: f32) -> Vec<EntityAnimations> {
+        self.entities()
+            .map(|entity| EntityAnimations {
+                entity,
+                animations: Animation::to(Opacity(opacity)).into(),
+            })
+            .collect()
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.entities()
+            .map(EntityAnimations::show_creation)
+            .collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.entities().map(EntityAnimations::fade_in).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.entities().map(EntityAnimations::fade_out).collect()
+    }
+
+    fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.number
+            .iter()
+            .chain(self.tokens.iter().map(|(_, id)| id))
+            .map(|id| id.0)
+    }
+}
+
+/// Block of code made with [CodeBuilder].
+#[derive(Debug, Clone)]
+pub struct CodeId {
+    group: EmptyId,
+    background: RectangleId,
+    lines: Vec<CodeLineId>,
+}
+
+impl CodeId {
+    /// [Empty](crate::Empty) object holding every object of the block as
+    /// children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn background(&self) -> RectangleId {
+        self.background
+    }
+    pub fn lines(&self) -> &[CodeLineId] {
+        &self.lines
+    }
+    /// Line at the given index, starting from zero whatever the
+    /// [first line number](CodeBuilder::with_first_line).
+    pub fn line(&self, index: usize) -> Option<&CodeLineId> {
+        self.lines.get(index)
+    }
+
+    /// Show the band behind the lines at the given indices and dim the
+    /// other lines.
+    pub fn highlight(&self, lines: &[usize]) -> Vec<EntityAnimations> {
+        let mut animations = Vec::new();
+        for (i, line) in self.lines.iter().enumerate() {
+            if lines.contains(&i) {
+                animations.push(line.band.fade_in());
+                animations.extend(line.set_opacity(1.0));
+            } else {
+                animations.push(line.band.fade_out());
+                animations.extend(line.set_opacity(0.35));
+            }
+        }
+        animations
+    }
+    /// Hide every band and restore the opacity of all lines.
+    pub fn clear_highlight(&self) -> Vec<EntityAnimations> {
+        let mut animations = Vec::new();
+        for line in self.lines.iter() {
+            animations.push(line.band.fade_out());
+            animations.extend(line.set_opacity(1.0));
+        }
+        animations
+    }
+
+    /// Morph every line into the line at the same index in `other`, and
+    /// the background into the background of `other`. Extra lines of
+    /// `other` fade in, and extra lines of this block fade out.
+    pub fn morph(&self, other: &CodeId) -> Vec<EntityAnimations> {
+        let mut animations = vec![self.background.morph(other.background)];
+        for (i, line) in self.lines.iter().enumerate() {
+            match other.lines.get(i) {
+                Some(target) => animations.extend(line.morph(target)),
+                None => animations.extend(line.fade_out()),
+            }
+        }
+        for line in other.lines.iter().skip(self.lines.len()) {
+            animations.extend(line.fade_in());
+        }
+        animations
+    }
+
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        let mut animations = vec![self.background.fade_in()];
+        for line in self.lines.iter() {
+            animations.extend(line.show_creation());
+        }
+        animations
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        let mut animations = vec![self.background.fade_in()];
+        for line in self.lines.iter() {
+            animations.extend(line.fade_in());
+        }
+        animations
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        let mut animations = vec![self.background.fade_out()];
+        for line in self.lines.iter() {
+            animations.push(line.band.fade_out());
+            animations.extend(line.fade_out());
+        }
+        animations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_align_to_tab_stops() {
+        assert_eq!(columns("ab", 4), vec![0, 1, 2]);
+        assert_eq!(columns("\tx", 4), vec![0, 4, 5]);
+        assert_eq!(columns("ab\tx\t", 4), vec![0, 1, 2, 4, 5, 8]);
+    }
+}
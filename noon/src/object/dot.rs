@@ -0,0 +1,120 @@
+use super::common::*;
+use crate::{Circle, ZOOM};
+
+/// Component indicating a dot, i.e. a small filled circle whose radius
+/// is specified in pixels on screen rather than in scene units.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Dot;
+
+impl PathComponent for Dot {
+    fn path(&self, size: &Size) -> Path {
+        Circle.path(size)
+    }
+}
+
+pub struct DotBuilder<'a> {
+    radius: f32,
+    color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> DotBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        Self {
+            radius: 6.0,
+            color: Color::random(),
+            position: Default::default(),
+            scene,
+        }
+    }
+    /// Radius of the dot in pixels.
+    pub fn with_radius(mut self, pixels: f32) -> Self {
+        self.radius = pixels;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+crate::position_builder!(DotBuilder);
+
+impl Create<DotId> for DotBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> DotId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let size = Size::from_radius(self.radius / ZOOM);
+        let path = Dot.path(&size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(Dot)
+            .insert(size)
+            .insert(BoundingSize(size))
+            .insert(scale)
+            .insert(self.position)
+            .insert(Angle::default())
+            .insert(StrokeWeight::NONE)
+            .insert(StrokeColor(self.color))
+            .insert(FillColor(self.color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(true))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn dot(scene: &mut Scene) -> DotBuilder<'_> {
+    DotBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct DotId(pub(crate) Entity);
+crate::into_entity!(DotId);
+
+impl WithStroke for DotId {}
+impl WithSize for DotId {}
+impl WithFill for DotId {}
+impl WithColor for DotId {}
+impl WithPath for DotId {}
+impl WithPosition for DotId {}
+impl WithStrokeWeight for DotId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dot_path_follows_size() {
+        let size = Size::from_radius(6.0 / ZOOM);
+        assert!((size.width - 12.0 / ZOOM).abs() < 1.0e-6);
+        let path = Dot.path(&size).size();
+        assert!((path.width - size.width).abs() < 1.0e-3);
+        assert!((path.height - size.height).abs() < 1.0e-3);
+
+        let path = Dot.path(&Size::from_radius(1.0)).size();
+        assert!((path.width - 2.0).abs() < 1.0e-3);
+        assert!((path.height - 2.0).abs() < 1.0e-3);
+    }
+}
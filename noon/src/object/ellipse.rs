@@ -0,0 +1,124 @@
+use super::common::*;
+use core::f32::consts::TAU;
+use nannou::lyon::math::Vector;
+
+/// Component indicating an ellipse, with its radii given by half of its [Size].
+#[derive(Component)]
+pub struct Ellipse;
+
+impl PathComponent for Ellipse {
+    fn path(&self, size: &Size) -> Path {
+        let radii = Vector::new(size.width / 2.0, size.height / 2.0);
+        let mut builder = Path::svg_builder();
+        let sweep_angle = nannou::lyon::math::Angle::radians(TAU);
+        let x_rotation = nannou::lyon::math::Angle::radians(0.0);
+
+        builder.move_to(point(radii.x, 0.0));
+        builder.arc(point(0.0, 0.0), radii, sweep_angle, x_rotation);
+        builder.close();
+
+        Path::new(builder.build(), true)
+    }
+}
+
+pub struct EllipseBuilder<'a> {
+    size: Size,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    position: Position,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> EllipseBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        let fill_color = Color::random();
+        Self {
+            size: Size::from(1.0, 0.5),
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            position: Default::default(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    /// Horizontal and vertical radii of the ellipse.
+    pub fn with_radii(mut self, x: f32, y: f32) -> Self {
+        self.size = Size::from(x * 2.0, y * 2.0);
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::angle_builder!(EllipseBuilder);
+crate::stroke_builder!(EllipseBuilder);
+crate::position_builder!(EllipseBuilder);
+crate::fill_builder!(EllipseBuilder);
+crate::size_builder!(EllipseBuilder);
+
+impl Create<EllipseId> for EllipseBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> EllipseId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let path = Ellipse.path(&self.size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(self.angle)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(Ellipse)
+            .insert(self.size)
+            .insert(BoundingSize(self.size))
+            .insert(scale)
+            .insert(self.position)
+            .insert(self.angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.fill_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(true))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn ellipse(scene: &mut Scene) -> EllipseBuilder<'_> {
+    EllipseBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct EllipseId(pub(crate) Entity);
+crate::into_entity!(EllipseId);
+
+impl WithStroke for EllipseId {}
+impl WithFill for EllipseId {}
+impl WithColor for EllipseId {}
+impl WithPath for EllipseId {}
+impl WithPosition for EllipseId {}
+impl WithAngle for EllipseId {}
+impl WithSize for EllipseId {}
+impl WithStrokeWeight for EllipseId {}
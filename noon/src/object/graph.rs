@@ -0,0 +1,416 @@
+use super::common::*;
+use super::path::insert_path;
+use crate::{AxesId, PathId};
+use nannou::lyon::geom::Translation;
+use std::ops::Range;
+
+/// Adaptive sampling of a curve `t -> point`.
+///
+/// The parameter range is first split into `samples` equal intervals, each
+/// of which is bisected until the midpoint lies within `tolerance` of the
+/// chord, and not too close to either end. Intervals that still fail after
+/// `max_depth` bisections are treated as jumps, and the curve is split
+/// there. Parameters where the curve is undefined (`None`) also split the
+/// curve.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Sampler {
+    pub(crate) tolerance: f32,
+    pub(crate) samples: usize,
+    pub(crate) max_depth: u32,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self {
+            tolerance: 1.0e-3,
+            samples: 32,
+            max_depth: 12,
+        }
+    }
+}
+
+/// Continuous pieces of a sampled curve.
+struct Pieces(Vec<Vec<Point>>);
+
+impl Pieces {
+    fn push(&mut self, p: Point) {
+        if let Some(piece) = self.0.last_mut() {
+            piece.push(p);
+        }
+    }
+    fn split(&mut self) {
+        if self.0.last().is_none_or(|piece| !piece.is_empty()) {
+            self.0.push(Vec::new());
+        }
+    }
+}
+
+impl Sampler {
+    pub(crate) fn sample(
+        &self,
+        f: &dyn Fn(f32) -> Option<Point>,
+        range: Range<f32>,
+    ) -> Vec<Vec<Point>> {
+        let n = self.samples.max(1);
+        let step = (range.end - range.start) / n as f32;
+        let mut pieces = Pieces(vec![Vec::new()]);
+
+        let mut a = range.start;
+        let mut pa = f(a);
+        if let Some(p) = pa {
+            pieces.push(p);
+        }
+        for i in 1..=n {
+            let b = range.start + step * i as f32;
+            let pb = f(b);
+            self.refine(f, (a, pa), (b, pb), 0, &mut pieces);
+            a = b;
+            pa = pb;
+        }
+
+        pieces.0.retain(|piece| piece.len() > 1);
+        pieces.0
+    }
+
+    /// Whether `pm` lies close to the chord from `pa` to `pb`, and roughly
+    /// halfway along it. Near a jump, the midpoint ends up next to one of
+    /// the two ends however small the interval gets.
+    fn is_flat(&self, pa: Point, pm: Point, pb: Point) -> bool {
+        let chord = (pb - pa).length();
+        let longest = (pm - pa).length().max((pb - pm).length());
+        distance_to_segment(pm, pa, pb) <= self.tolerance && longest <= 0.9 * chord + self.tolerance
+    }
+
+    /// Add the points of the curve after `a` up to and including `b`.
+    fn refine(
+        &self,
+        f: &dyn Fn(f32) -> Option<Point>,
+        (a, pa): (f32, Option<Point>),
+        (b, pb): (f32, Option<Point>),
+        depth: u32,
+        pieces: &mut Pieces,
+    ) {
+        let mid = (a + b) / 2.0;
+        match (pa, pb) {
+            (Some(pa), Some(pb)) => {
+                let pm = f(mid);
+                match pm {
+                    Some(pm) if self.is_flat(pa, pm, pb) => {
+                        pieces.push(pm);
+                        pieces.push(pb);
+                    }
+                    _ if depth < self.max_depth => {
+                        self.refine(f, (a, Some(pa)), (mid, pm), depth + 1, pieces);
+                        self.refine(f, (mid, pm), (b, Some(pb)), depth + 1, pieces);
+                    }
+                    _ => {
+                        pieces.split();
+                        pieces.push(pb);
+                    }
+                }
+            }
+            (None, None) => pieces.split(),
+            // Locate the edge of the region where the curve is defined.
+            _ if depth < self.max_depth => {
+                let pm = f(mid);
+                self.refine(f, (a, pa), (mid, pm), depth + 1, pieces);
+                self.refine(f, (mid, pm), (b, pb), depth + 1, pieces);
+            }
+            (Some(_), None) => pieces.split(),
+            (None, Some(pb)) => {
+                pieces.split();
+                pieces.push(pb);
+            }
+        }
+    }
+}
+
+fn distance_to_segment(p: Point, from: Point, to: Point) -> f32 {
+    let d = to - from;
+    let t = if d.square_length() > 0.0 {
+        ((p - from).dot(d) / d.square_length()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p - (from + d * t)).length()
+}
+
+/// Build a path from the pieces of a sampled curve, centered around
+/// the center of its bounding box.
+pub(crate) fn pieces_to_path(pieces: &[Vec<Point>], closed: bool) -> (Path, Position) {
+    let points: Vec<Point> = pieces.iter().flatten().copied().collect();
+    let position = match points.first() {
+        Some(&first) => {
+            let (min, max) = points
+                .iter()
+                .fold((first, first), |(min, max), p| (min.min(*p), max.max(*p)));
+            Position {
+                x: (min.x + max.x) / 2.0,
+                y: (min.y + max.y) / 2.0,
+            }
+        }
+        None => Position::default(),
+    };
+
+    let mut builder = Path::builder();
+    if pieces.is_empty() {
+        builder.begin(point(0.0, 0.0));
+        builder.end(false);
+    }
+    for piece in pieces {
+        builder.begin(piece[0]);
+        for p in piece.iter().skip(1) {
+            builder.line_to(*p);
+        }
+        builder.end(closed);
+    }
+    let raw = builder
+        .build()
+        .transformed(&Translation::new(-position.x, -position.y));
+
+    (Path::new(raw, closed), position)
+}
+
+/// Builder for the graph `y = f(x)` of a function over a range of `x`.
+///
+/// The function is sampled adaptively, so that the graph is sampled more
+/// densely where it bends. The graph is split wherever the function is
+/// undefined (`NaN` or infinite) or jumps, e.g. at the asymptotes of `tan`.
+pub struct FunctionGraphBuilder<'a> {
+    function: Box<dyn Fn(f32) -> f32 + 'a>,
+    x_range: Range<f32>,
+    y_range: Option<Range<f32>>,
+    sampler: Sampler,
+    axes: Option<AxesId>,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    scene: &'a mut Scene,
+}
+
+impl<'a> FunctionGraphBuilder<'a> {
+    fn new(scene: &'a mut Scene, function: impl Fn(f32) -> f32 + 'a, x_range: Range<f32>) -> Self {
+        Self {
+            function: Box::new(function),
+            x_range,
+            y_range: None,
+            sampler: Sampler::default(),
+            axes: None,
+            stroke_weight: StrokeWeight::THICK,
+            stroke_color: Color::random(),
+            scene,
+        }
+    }
+    /// Leave out the parts of the graph outside of the given range of `y`.
+    pub fn with_y_range(mut self, y_range: Range<f32>) -> Self {
+        self.y_range = Some(y_range);
+        self
+    }
+    /// Maximum distance between the sampled path and the actual graph.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.sampler.tolerance = tolerance;
+        self
+    }
+    /// Number of evenly spaced samples taken before adaptive refinement.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.sampler.samples = samples;
+        self
+    }
+    /// Plot the graph in the data coordinates of the given axes, and make
+    /// it move along with them.
+    pub fn on_axes(mut self, axes: &AxesId) -> Self {
+        self.axes = Some(axes.clone());
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+}
+
+crate::stroke_builder!(FunctionGraphBuilder);
+
+impl Create<PathId> for FunctionGraphBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> PathId {
+        let f = &self.function;
+        let y_range = self.y_range.clone();
+        let coords = self.axes.as_ref().map(|axes| axes.coordinates(self.scene));
+        let pieces = self.sampler.sample(
+            &|x| {
+                let y = f(x);
+                let inside = y_range.as_ref().is_none_or(|r| r.contains(&y));
+                (y.is_finite() && inside).then(|| match &coords {
+                    Some(coords) => coords.coords_to_point(x, y),
+                    None => point(x, y),
+                })
+            },
+            self.x_range.clone(),
+        );
+        let (path, position) = pieces_to_path(&pieces, false);
+
+        let id = insert_path(
+            self.scene,
+            path,
+            position,
+            Angle::default(),
+            self.stroke_weight,
+            self.stroke_color,
+            None,
+        );
+        if let Some(axes) = &self.axes {
+            axes.add(self.scene, id);
+        }
+        id
+    }
+}
+
+pub fn function_graph<'a>(
+    scene: &'a mut Scene,
+    function: impl Fn(f32) -> f32 + 'a,
+    x_range: Range<f32>,
+) -> FunctionGraphBuilder<'a> {
+    FunctionGraphBuilder::new(scene, function, x_range)
+}
+
+/// Builder for a plane curve given by a parametric function `t -> (x, y)`
+/// over a range of `t`, sampled adaptively like [FunctionGraphBuilder].
+pub struct ParametricCurveBuilder<'a> {
+    function: Box<dyn Fn(f32) -> (f32, f32) + 'a>,
+    t_range: Range<f32>,
+    sampler: Sampler,
+    closed: bool,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    scene: &'a mut Scene,
+}
+
+impl<'a> ParametricCurveBuilder<'a> {
+    fn new(
+        scene: &'a mut Scene,
+        function: impl Fn(f32) -> (f32, f32) + 'a,
+        t_range: Range<f32>,
+    ) -> Self {
+        let fill_color = Color::random();
+        Self {
+            function: Box::new(function),
+            t_range,
+            sampler: Sampler::default(),
+            closed: false,
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            scene,
+        }
+    }
+    /// Maximum distance between the sampled path and the actual curve.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.sampler.tolerance = tolerance;
+        self
+    }
+    /// Number of evenly spaced samples taken before adaptive refinement.
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.sampler.samples = samples;
+        self
+    }
+    /// Join the end of the curve back to its start, so that it can be filled.
+    pub fn close(mut self) -> Self {
+        self.closed = true;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::stroke_builder!(ParametricCurveBuilder);
+crate::fill_builder!(ParametricCurveBuilder);
+
+impl Create<PathId> for ParametricCurveBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> PathId {
+        let f = &self.function;
+        let pieces = self.sampler.sample(
+            &|t| {
+                let (x, y) = f(t);
+                (x.is_finite() && y.is_finite()).then(|| point(x, y))
+            },
+            self.t_range.clone(),
+        );
+        let (path, position) = pieces_to_path(&pieces, self.closed);
+
+        insert_path(
+            self.scene,
+            path,
+            position,
+            Angle::default(),
+            self.stroke_weight,
+            self.stroke_color,
+            self.closed.then_some(self.fill_color),
+        )
+    }
+}
+
+pub fn parametric_curve<'a>(
+    scene: &'a mut Scene,
+    function: impl Fn(f32) -> (f32, f32) + 'a,
+    t_range: Range<f32>,
+) -> ParametricCurveBuilder<'a> {
+    ParametricCurveBuilder::new(scene, function, t_range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TAU;
+
+    fn graph(f: impl Fn(f32) -> f32, range: Range<f32>) -> Vec<Vec<Point>> {
+        Sampler::default().sample(
+            &|x| {
+                let y = f(x);
+                y.is_finite().then(|| point(x, y))
+            },
+            range,
+        )
+    }
+
+    #[test]
+    fn samples_densely_where_curved() {
+        let pieces = graph(|x| (x * 4.0).tanh(), -2.0..2.0);
+        assert_eq!(pieces.len(), 1);
+        let points = &pieces[0];
+        let near = |c: f32| points.iter().filter(|p| (p.x - c).abs() < 0.25).count();
+        assert!(near(0.5) > 2 * near(1.75));
+        assert_eq!(points.first().unwrap().x, -2.0);
+        assert_eq!(points.last().unwrap().x, 2.0);
+    }
+
+    #[test]
+    fn splits_at_jumps_and_undefined_values() {
+        let steps = graph(|x| x.floor(), 0.1..2.9);
+        assert_eq!(steps.len(), 3);
+        let sqrt = graph(|x| x.sqrt(), -1.0..1.0);
+        assert_eq!(sqrt.len(), 1);
+        assert!(sqrt[0][0].x.abs() < 1.0e-3);
+        let tan = graph(|x| x.tan(), -3.0..3.0);
+        assert_eq!(tan.len(), 3);
+    }
+
+    #[test]
+    fn closed_parametric_curve() {
+        let circle = Sampler::default().sample(&|t| Some(point(t.cos(), t.sin())), 0.0..TAU);
+        assert_eq!(circle.len(), 1);
+        let (path, position) = pieces_to_path(&circle, true);
+        assert!(path.closed);
+        assert!(position.x.abs() < 1.0e-3 && position.y.abs() < 1.0e-3);
+        let size = path.size();
+        assert!((size.width - 2.0).abs() < 1.0e-2);
+        assert!((size.height - 2.0).abs() < 1.0e-2);
+    }
+}
@@ -5,7 +5,7 @@ use nannou::lyon::path::traits::PathBuilder;
 pub struct Line;
 
 impl Line {
-    fn path(points: &[Point]) -> (Path, Position) {
+    fn path(points: &[Point], tension: Option<f32>, closed: bool) -> (Path, Position) {
         let centroid = Position::from_points(&points);
 
         let mut builder = Path::builder();
@@ -14,10 +14,17 @@ impl Line {
                 .get(0)
                 .expect("Attempted to create a line with 0 points"),
         );
-        for &p in points.iter().skip(1) {
-            builder.line_to(p);
+        match tension {
+            Some(tension) if points.len() > 2 => {
+                Self::add_curves(&mut builder, points, tension, closed)
+            }
+            _ => {
+                for &p in points.iter().skip(1) {
+                    builder.line_to(p);
+                }
+            }
         }
-        builder.end(false);
+        builder.end(closed);
 
         // Translate the points so that they are centered around point (0,0)
         let path = builder
@@ -27,18 +34,39 @@ impl Line {
                 -centroid.y,
             ));
 
-        (
-            Path {
-                raw: path,
-                closed: false,
-            },
-            centroid,
-        )
+        (Path { raw: path, closed }, centroid)
+    }
+
+    /// Join the points with cubic Bezier curves of a cardinal spline. A
+    /// `tension` of 0 gives a Catmull-Rom spline, while 1 gives straight lines.
+    fn add_curves(
+        builder: &mut nannou::lyon::path::path::Builder,
+        points: &[Point],
+        tension: f32,
+        closed: bool,
+    ) {
+        let n = points.len();
+        let get = |i: isize| -> Point {
+            if closed {
+                points[i.rem_euclid(n as isize) as usize]
+            } else {
+                points[i.clamp(0, n as isize - 1) as usize]
+            }
+        };
+        let tangent = |i: isize| (get(i + 1) - get(i - 1)) * (1.0 - tension) / 2.0;
+        let segments = if closed { n } else { n - 1 };
+        for i in 0..segments as isize {
+            let ctrl1 = get(i) + tangent(i) / 3.0;
+            let ctrl2 = get(i + 1) - tangent(i + 1) / 3.0;
+            builder.cubic_bezier_to(ctrl1, ctrl2, get(i + 1));
+        }
     }
 }
 
 pub struct LineBuilder<'a> {
     points: Vec<Point>,
+    tension: Option<f32>,
+    closed: bool,
     stroke_color: Color,
     stroke_weight: StrokeWeight,
     angle: Angle,
@@ -49,6 +77,8 @@ impl<'a> LineBuilder<'a> {
     fn new(scene: &'a mut Scene) -> Self {
         Self {
             points: Vec::new(),
+            tension: None,
+            closed: false,
             stroke_weight: StrokeWeight::THICK,
             stroke_color: Color::random(),
             angle: Default::default(),
@@ -69,6 +99,22 @@ impl<'a> LineBuilder<'a> {
         self.points.push(point);
         self
     }
+    /// Join the points with a smooth Catmull-Rom curve instead of straight lines.
+    pub fn smooth(mut self) -> Self {
+        self.tension = Some(0.0);
+        self
+    }
+    /// Join the points with a smooth curve of given tension, from 0 for a
+    /// Catmull-Rom curve to 1 for straight lines.
+    pub fn with_tension(mut self, tension: f32) -> Self {
+        self.tension = Some(tension);
+        self
+    }
+    /// Connect the last point back to the first one.
+    pub fn close(mut self) -> Self {
+        self.closed = true;
+        self
+    }
     pub fn with_color(mut self, color: Color) -> Self {
         self.stroke_color = color;
         self
@@ -86,13 +132,17 @@ impl Create<LineId> for LineBuilder<'_> {
         let world = self.scene.world.get_mut();
 
         let scale = Scale::ONE;
-        let (path, position) = Line::path(&self.points);
+        let (path, position) = Line::path(&self.points, self.tension, self.closed);
+        let size = if self.tension.is_some() {
+            path.size()
+        } else {
+            Size::from_points(&self.points)
+        };
         let transform = Transform::identity()
             .scale(scale)
             .rotate(self.angle)
             .translate(position.into());
         let screen_transform = self.scene.transform;
-        let size = Size::from_points(&self.points);
 
         let global_path = PixelPath(
             path.clone()
@@ -103,6 +153,7 @@ impl Create<LineId> for LineBuilder<'_> {
             .spawn_empty()
             .insert(Line)
             .insert(size)
+            .insert(BoundingSize(size))
             .insert(scale)
             .insert(position)
             .insert(self.angle)
@@ -126,6 +177,13 @@ pub fn line(scene: &mut Scene) -> LineBuilder {
     LineBuilder::new(scene)
 }
 
+/// Smooth curve passing through all the given points.
+pub fn curve_through<'a>(scene: &'a mut Scene, points: &[Point]) -> LineBuilder<'a> {
+    let mut builder = LineBuilder::new(scene).smooth();
+    builder.points = points.to_vec();
+    builder
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct LineId(pub(crate) Entity);
 crate::into_entity!(LineId);
@@ -135,3 +193,32 @@ impl WithPath for LineId {}
 impl WithPosition for LineId {}
 impl WithAngle for LineId {}
 impl WithStrokeWeight for LineId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::lyon::path::PathEvent;
+
+    #[test]
+    fn smooth_line_passes_through_points() {
+        let points = [
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(2.0, 0.0),
+            point(3.0, 1.0),
+        ];
+        let (path, centroid) = Line::path(&points, Some(0.0), false);
+        let ends: Vec<Point> = path
+            .raw
+            .iter()
+            .filter_map(|e| match e {
+                PathEvent::Cubic { to, .. } => Some(point(to.x + centroid.x, to.y + centroid.y)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ends.len(), 3);
+        for (end, p) in ends.iter().zip(points.iter().skip(1)) {
+            assert!((*end - *p).length() < 1.0e-5);
+        }
+    }
+}
@@ -0,0 +1,608 @@
+use super::parse::{Class, Expr, Kind};
+use crate::Point;
+use nannou::lyon::algorithms::aabb::bounding_rect;
+use nannou::lyon::math::{point, Transform as LyonTransform, Vector};
+use nannou::lyon::path::Path as RawPath;
+use nannou::text::{glyph, Font, GlyphId};
+
+/// Size of scripts relative to the base font size, by nesting level.
+const SCRIPT_SCALE: [f32; 3] = [1.0, 0.7, 0.5];
+/// Horizontal shear of italic glyphs.
+const SLANT: f32 = 0.2;
+
+/// Outline of part of a formula, in formula coordinates with the baseline
+/// at `y = 0`.
+#[derive(Clone)]
+pub(crate) struct Piece {
+    pub(crate) path: RawPath,
+    /// Address of the node of the syntax tree drawing this piece.
+    pub(crate) address: Vec<usize>,
+}
+
+/// Box of typeset material, with its origin on the baseline at the left.
+#[derive(Clone, Default)]
+pub(crate) struct Layout {
+    pub(crate) width: f32,
+    pub(crate) ascent: f32,
+    pub(crate) descent: f32,
+    pub(crate) pieces: Vec<Piece>,
+}
+
+impl Layout {
+    fn piece(path: RawPath, address: &[usize]) -> Self {
+        let (ascent, descent) = match path.iter().next() {
+            Some(_) => {
+                let rect = bounding_rect(path.iter());
+                (rect.max_y().max(0.0), (-rect.min_y()).max(0.0))
+            }
+            None => (0.0, 0.0),
+        };
+        Self {
+            width: 0.0,
+            ascent,
+            descent,
+            pieces: vec![Piece {
+                path,
+                address: address.to_vec(),
+            }],
+        }
+    }
+
+    /// Add `other` with its origin at `(x, y)`, growing the box to fit.
+    fn add(&mut self, other: Layout, x: f32, y: f32) {
+        self.ascent = self.ascent.max(other.ascent + y);
+        self.descent = self.descent.max(other.descent - y);
+        self.width = self.width.max(x + other.width);
+        let offset = LyonTransform::translation(x, y);
+        self.pieces
+            .extend(other.pieces.into_iter().map(|piece| Piece {
+                path: piece.path.transformed(&offset),
+                address: piece.address,
+            }));
+    }
+}
+
+/// Closed outline of a polyline drawn with the given width.
+fn thick_polyline(points: &[Point], width: f32) -> RawPath {
+    let mut builder = RawPath::builder();
+    if points.len() < 2 {
+        return builder.build();
+    }
+    let half = width / 2.0;
+    let normal = |a: Point, b: Point| {
+        let d = (b - a).normalize();
+        Vector::new(-d.y, d.x)
+    };
+    let offsets: Vec<Vector> = (0..points.len())
+        .map(|i| {
+            let before = (i > 0).then(|| normal(points[i - 1], points[i]));
+            let after = (i + 1 < points.len()).then(|| normal(points[i], points[i + 1]));
+            match (before, after) {
+                (Some(n1), Some(n2)) => {
+                    let miter = (n1 + n2).normalize();
+                    let cos = miter.dot(n1).max(0.3);
+                    miter * half / cos
+                }
+                (Some(n), None) | (None, Some(n)) => n * half,
+                (None, None) => Vector::zero(),
+            }
+        })
+        .collect();
+    builder.begin(points[0] + offsets[0]);
+    for (p, o) in points.iter().zip(offsets.iter()).skip(1) {
+        builder.line_to(*p + *o);
+    }
+    for (p, o) in points.iter().zip(offsets.iter()).rev() {
+        builder.line_to(*p - *o);
+    }
+    builder.close();
+    builder.build()
+}
+
+fn rectangle(x0: f32, y0: f32, x1: f32, y1: f32) -> RawPath {
+    let mut builder = RawPath::builder();
+    builder.begin(point(x0, y0));
+    builder.line_to(point(x1, y0));
+    builder.line_to(point(x1, y1));
+    builder.line_to(point(x0, y1));
+    builder.close();
+    builder.build()
+}
+
+/// Spacing in ems between adjacent atoms of given classes. Only spacing
+/// around operators and functions remains in scripts.
+fn spacing(left: Class, right: Class, script: bool) -> f32 {
+    use Class::*;
+    let eighteenths = match (left, right) {
+        (Op, Ord) | (Ord, Op) | (Op, Op) | (Close, Op) => 3.0,
+        _ if script => 0.0,
+        (Bin, _) | (_, Bin) => 4.0,
+        (Rel, Rel) | (Open, Rel) | (Rel, Close) | (Rel, Punct) => 0.0,
+        (Rel, _) | (_, Rel) => 5.0,
+        (Punct, _) => 3.0,
+        _ => 0.0,
+    };
+    eighteenths / 18.0
+}
+
+/// Style of a sub-formula: scripts are smaller, and display style allows
+/// limits above and below large operators.
+#[derive(Debug, Clone, Copy)]
+struct Style {
+    level: usize,
+    display: bool,
+}
+
+impl Style {
+    fn script(self) -> Self {
+        Self {
+            level: self.level + 1,
+            display: false,
+        }
+    }
+    fn fraction(self) -> Self {
+        if self.display {
+            Self {
+                display: false,
+                ..self
+            }
+        } else {
+            self.script()
+        }
+    }
+}
+
+/// Typesetting of a formula from its syntax tree.
+pub(crate) struct Typesetter<'a> {
+    fonts: &'a [Font],
+    size: f32,
+    /// Height of the math axis, on which fractions bars and operators are
+    /// centered, as a ratio of the font size.
+    axis: f32,
+}
+
+impl<'a> Typesetter<'a> {
+    pub(crate) fn new(fonts: &'a [Font], size: f32) -> Self {
+        let mut typesetter = Self {
+            fonts,
+            size,
+            axis: 0.25,
+        };
+        let plus = typesetter.glyph('+', 1.0, false, &[]);
+        if plus.ascent > 0.0 {
+            typesetter.axis = (plus.ascent - plus.descent) / 2.0;
+        }
+        typesetter
+    }
+
+    pub(crate) fn layout(&self, expr: &Expr) -> Layout {
+        let style = Style {
+            level: 0,
+            display: true,
+        };
+        self.node(expr, style, &mut Vec::new())
+    }
+
+    fn size(&self, style: Style) -> f32 {
+        self.size * SCRIPT_SCALE[style.level.min(SCRIPT_SCALE.len() - 1)]
+    }
+
+    fn glyph(&self, ch: char, size: f32, italic: bool, address: &[usize]) -> Layout {
+        if self.fonts.is_empty() {
+            return Layout::default();
+        }
+        let font = self
+            .fonts
+            .iter()
+            .position(|f| f.glyph(ch).id() != GlyphId(0))
+            .unwrap_or(0);
+        let scaled = self.fonts[font]
+            .glyph(ch)
+            .scaled(nannou::text::Scale::uniform(size));
+        let advance = scaled.h_metrics().advance_width;
+        let shear = if italic { SLANT } else { 0.0 };
+        let transform = LyonTransform::new(1.0, 0.0, shear, 1.0, 0.0, 0.0);
+        let mut builder = RawPath::builder();
+        for event in glyph::path_events(scaled).into_iter().flatten() {
+            builder.path_event(event.transformed(&transform));
+        }
+        let path = builder.build();
+        if path.iter().next().is_none() {
+            return Layout {
+                width: advance,
+                ..Default::default()
+            };
+        }
+        let mut layout = Layout::piece(path, address);
+        // Leave room for the slanted top of italic glyphs.
+        layout.width = advance + shear * layout.ascent * 0.3;
+        layout
+    }
+
+    fn word(&self, text: &str, size: f32, address: &[usize]) -> Layout {
+        let mut layout = Layout::default();
+        for ch in text.chars() {
+            let glyph = self.glyph(ch, size, false, address);
+            let x = layout.width;
+            layout.width += glyph.width;
+            layout.add(glyph, x, 0.0);
+        }
+        layout
+    }
+
+    fn class(expr: &Expr) -> Option<Class> {
+        match &expr.kind {
+            Kind::Symbol { class, .. } | Kind::Word { class, .. } => Some(*class),
+            Kind::BigDelim { class, .. } => Some(*class),
+            Kind::BigOp { .. } => Some(Class::Op),
+            Kind::Space(_) => None,
+            Kind::Scripts => expr.children.first().and_then(Self::class),
+            Kind::Group if expr.children.len() == 1 => Self::class(&expr.children[0]),
+            _ => Some(Class::Ord),
+        }
+    }
+
+    fn list(&self, children: &[Expr], style: Style, address: &mut Vec<usize>) -> Layout {
+        let size = self.size(style);
+        let mut classes: Vec<Option<Class>> = children.iter().map(Self::class).collect();
+        // Binary operators without operands on both sides are ordinary symbols.
+        for i in 0..classes.len() {
+            if classes[i] == Some(Class::Bin) {
+                let before = i.checked_sub(1).and_then(|j| classes[j]);
+                let after = classes.get(i + 1).copied().flatten();
+                let unary = matches!(
+                    before,
+                    None | Some(Class::Bin | Class::Op | Class::Rel | Class::Open | Class::Punct)
+                ) || matches!(
+                    after,
+                    None | Some(Class::Rel | Class::Close | Class::Punct)
+                );
+                if unary {
+                    classes[i] = Some(Class::Ord);
+                }
+            }
+        }
+
+        let mut layout = Layout::default();
+        let mut previous = None;
+        for (i, (child, class)) in children.iter().zip(classes).enumerate() {
+            if let (Some(left), Some(right)) = (previous, class) {
+                layout.width += spacing(left, right, style.level > 0) * size;
+            }
+            address.push(i);
+            let child = self.node(child, style, address);
+            address.pop();
+            let x = layout.width;
+            layout.width += child.width;
+            layout.add(child, x, 0.0);
+            previous = class;
+        }
+        layout
+    }
+
+    fn node(&self, expr: &Expr, style: Style, address: &mut Vec<usize>) -> Layout {
+        let size = self.size(style);
+        match &expr.kind {
+            Kind::Symbol { ch, italic, .. } => self.glyph(*ch, size, *italic, address),
+            Kind::Word { text, .. } => self.word(text, size, address),
+            Kind::BigOp { ch, .. } => {
+                let scale = if style.display { 1.6 } else { 1.2 };
+                let mut glyph = self.glyph(*ch, size * scale, false, address);
+                // Center the operator on the axis.
+                let shift = self.axis * size - (glyph.ascent - glyph.descent) / 2.0;
+                let mut layout = Layout {
+                    width: glyph.width,
+                    ..Default::default()
+                };
+                glyph.width = 0.0;
+                layout.add(glyph, 0.0, shift);
+                layout
+            }
+            Kind::Space(em) => Layout {
+                width: em * size,
+                ..Default::default()
+            },
+            Kind::Group => self.list(&expr.children, style, address),
+            Kind::Frac => self.fraction(expr, style, address),
+            Kind::Sqrt => self.root(expr, style, address),
+            Kind::Scripts => self.scripts(expr, style, address),
+            Kind::Delimited { left, right } => {
+                address.push(0);
+                let body = self.node(&expr.children[0], style, address);
+                address.pop();
+                let axis = self.axis * size;
+                let half = (body.ascent - axis)
+                    .max(body.descent + axis)
+                    .max(0.5 * size)
+                    + 0.1 * size;
+                let mut layout = self.delimiter(*left, axis, half, size, false, address);
+                let x = layout.width;
+                layout.width += body.width;
+                layout.add(body, x, 0.0);
+                let right = self.delimiter(*right, axis, half, size, true, address);
+                let x = layout.width;
+                layout.width += right.width;
+                layout.add(right, x, 0.0);
+                layout
+            }
+            Kind::BigDelim {
+                ch, size: scale, ..
+            } => {
+                let opening = !matches!(ch, ')' | ']' | '}' | '⟩');
+                self.delimiter(
+                    *ch,
+                    self.axis * size,
+                    scale * size / 2.0,
+                    size,
+                    !opening,
+                    address,
+                )
+            }
+        }
+    }
+
+    fn fraction(&self, expr: &Expr, style: Style, address: &mut Vec<usize>) -> Layout {
+        let size = self.size(style);
+        let inner = style.fraction();
+        address.push(0);
+        let numerator = self.node(&expr.children[0], inner, address);
+        address.pop();
+        address.push(1);
+        let denominator = self.node(&expr.children[1], inner, address);
+        address.pop();
+
+        let thickness = 0.05 * size;
+        let gap = 0.12 * size;
+        let pad = 0.1 * size;
+        let axis = self.axis * size;
+        let width = numerator.width.max(denominator.width) + 2.0 * pad;
+        let mut layout = Layout::piece(
+            rectangle(0.0, axis - thickness / 2.0, width, axis + thickness / 2.0),
+            address,
+        );
+        let up = axis + thickness / 2.0 + gap + numerator.descent;
+        let down = axis - thickness / 2.0 - gap - denominator.ascent;
+        let (x_numerator, x_denominator) = (
+            (width - numerator.width) / 2.0,
+            (width - denominator.width) / 2.0,
+        );
+        layout.add(numerator, x_numerator, up);
+        layout.add(denominator, x_denominator, down);
+        layout.width = width;
+        layout
+    }
+
+    fn root(&self, expr: &Expr, style: Style, address: &mut Vec<usize>) -> Layout {
+        let size = self.size(style);
+        address.push(0);
+        let radicand = self.node(&expr.children[0], style, address);
+        address.pop();
+        address.push(1);
+        let index = self.node(&expr.children[1], style.script().script(), address);
+        address.pop();
+
+        let thickness = 0.05 * size;
+        let gap = 0.12 * size;
+        let bottom = -radicand.descent.max(0.05 * size) - 0.05 * size;
+        let top = radicand.ascent.max(0.6 * size) + gap;
+        let height = top - bottom;
+        let sign = (0.3 * size).max(0.1 * height);
+        let tick = height.min(size);
+
+        // Move everything right if the index doesn't fit above the tick.
+        let shift = (index.width - 0.55 * sign).max(0.0);
+        let x = |x: f32| x + shift;
+        let points = [
+            point(x(0.0), bottom + 0.4 * tick),
+            point(x(0.2 * sign), bottom + 0.48 * tick),
+            point(x(0.5 * sign), bottom),
+            point(x(sign), top),
+            point(x(sign + radicand.width + 0.15 * size), top),
+        ];
+        let width = x(sign + radicand.width + 0.15 * size) + 0.05 * size;
+        let mut layout = Layout::piece(thick_polyline(&points, thickness), address);
+        layout.add(radicand, x(sign + 0.08 * size), 0.0);
+        if !index.pieces.is_empty() {
+            let y = bottom + 0.55 * tick + index.descent;
+            let x = x(0.55 * sign) - index.width;
+            layout.add(index, x, y);
+        }
+        layout.width = width;
+        layout
+    }
+
+    fn scripts(&self, expr: &Expr, style: Style, address: &mut Vec<usize>) -> Layout {
+        let size = self.size(style);
+        let limits = match &expr.children[0].kind {
+            Kind::BigOp { limits, .. } | Kind::Word { limits, .. } => *limits && style.display,
+            _ => false,
+        };
+        address.push(0);
+        let base = self.node(&expr.children[0], style, address);
+        address.pop();
+        let script_style = style.script();
+        address.push(1);
+        let sup = self.node(&expr.children[1], script_style, address);
+        address.pop();
+        address.push(2);
+        let sub = self.node(&expr.children[2], script_style, address);
+        address.pop();
+        let has_sup = !expr.children[1].children.is_empty();
+        let has_sub = !expr.children[2].children.is_empty();
+
+        let mut layout = Layout::default();
+        if limits {
+            let gap = 0.1 * size;
+            let width = base.width.max(sup.width).max(sub.width);
+            let (base_ascent, base_descent) = (base.ascent, base.descent);
+            let x = (width - base.width) / 2.0;
+            layout.add(base, x, 0.0);
+            if has_sup {
+                let (x, y) = ((width - sup.width) / 2.0, base_ascent + gap + sup.descent);
+                layout.add(sup, x, y);
+            }
+            if has_sub {
+                let (x, y) = ((width - sub.width) / 2.0, -base_descent - gap - sub.ascent);
+                layout.add(sub, x, y);
+            }
+            layout.width = width;
+            return layout;
+        }
+
+        let script_size = self.size(script_style);
+        let mut up = (base.ascent - 0.35 * script_size)
+            .max(0.4 * size)
+            .max(sup.descent + 0.2 * size);
+        let mut down = (base.descent + 0.1 * size).max(0.2 * size);
+        if has_sup && has_sub {
+            let clearance = (up - sup.descent) - (sub.ascent - down);
+            if clearance < 0.1 * size {
+                let extra = 0.1 * size - clearance;
+                up += extra / 2.0;
+                down += extra / 2.0;
+            }
+        } else if !has_sup {
+            up = 0.0;
+        }
+        let x = base.width + 0.03 * size;
+        let width = sup.width.max(sub.width);
+        layout.add(base, 0.0, 0.0);
+        if has_sup {
+            layout.add(sup, x, up);
+        }
+        if has_sub {
+            layout.add(sub, x, -down);
+        }
+        layout.width = x + width + 0.05 * size;
+        layout
+    }
+
+    /// Delimiter centered at height `center`, extending `half` above and
+    /// below it.
+    fn delimiter(
+        &self,
+        ch: char,
+        center: f32,
+        half: f32,
+        size: f32,
+        closing: bool,
+        address: &[usize],
+    ) -> Layout {
+        let thickness = 0.06 * size;
+        let margin = 0.05 * size;
+        let width = 0.15 * size + 0.05 * half;
+        let (top, bottom) = (center + half, center - half);
+        let points: Vec<Vec<Point>> = match ch {
+            '(' | ')' => {
+                // Arc of an ellipse through the ends and the middle.
+                let max = 1.0_f32;
+                vec![(0..=16)
+                    .map(|i| {
+                        let phi = -max + 2.0 * max * i as f32 / 16.0;
+                        let x = width * (1.0 - phi.cos()) / (1.0 - max.cos());
+                        point(x, center + half * phi.sin() / max.sin())
+                    })
+                    .collect()]
+            }
+            '[' | ']' => vec![vec![
+                point(width, top),
+                point(0.0, top),
+                point(0.0, bottom),
+                point(width, bottom),
+            ]],
+            '{' | '}' => {
+                let r = (0.25 * width).min(half / 4.0);
+                let mid = width / 2.0;
+                let corner = |c: Point, from: f32, to: f32| {
+                    (0..=4).map(move |i| {
+                        let a = from + (to - from) * i as f32 / 4.0;
+                        point(c.x + r * a.cos(), c.y + r * a.sin())
+                    })
+                };
+                use std::f32::consts::{FRAC_PI_2, PI};
+                let mut line = Vec::new();
+                line.extend(corner(point(mid + r, top - r), FRAC_PI_2, PI));
+                line.extend(corner(point(mid - r, center + r), 0.0, -FRAC_PI_2));
+                line.extend(corner(point(mid - r, center - r), FRAC_PI_2, 0.0));
+                line.extend(corner(point(mid + r, bottom + r), PI, 3.0 * FRAC_PI_2));
+                vec![line]
+            }
+            '⟨' | '⟩' => vec![vec![
+                point(width, top),
+                point(0.0, center),
+                point(width, bottom),
+            ]],
+            '|' => vec![vec![point(width / 2.0, top), point(width / 2.0, bottom)]],
+            '‖' => vec![
+                vec![point(width / 4.0, top), point(width / 4.0, bottom)],
+                vec![
+                    point(3.0 * width / 4.0, top),
+                    point(3.0 * width / 4.0, bottom),
+                ],
+            ],
+            '/' => vec![vec![point(0.0, bottom), point(width, top)]],
+            _ => return Layout::default(),
+        };
+        let mirror = if closing {
+            // Closing delimiters are mirror images of opening ones.
+            LyonTransform::new(-1.0, 0.0, 0.0, 1.0, width, 0.0)
+        } else {
+            LyonTransform::identity()
+        };
+        let shift = LyonTransform::translation(margin + thickness / 2.0, 0.0);
+        let mut layout = Layout::default();
+        for line in points {
+            let path = thick_polyline(&line, thickness)
+                .transformed(&mirror)
+                .transformed(&shift);
+            layout.add(Layout::piece(path, address), 0.0, 0.0);
+        }
+        layout.width = width + 2.0 * margin + thickness;
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse::Parser;
+    use super::*;
+    use crate::{FontFamily, Fonts};
+
+    fn layout(source: &str) -> Layout {
+        let fonts = Fonts::new().chain(&FontFamily::default());
+        let expr = Parser::new(source).parse().unwrap();
+        Typesetter::new(&fonts, 1.0).layout(&expr)
+    }
+
+    #[test]
+    fn fractions_stack_vertically() {
+        let single = layout("a");
+        let fraction = layout(r"\frac{a}{b}");
+        assert!(fraction.ascent > single.ascent + 0.3);
+        assert!(fraction.descent > single.descent + 0.3);
+        // Glyphs of a, b and the bar.
+        assert_eq!(fraction.pieces.len(), 3);
+        assert_eq!(fraction.pieces[0].address, vec![0]);
+        assert_eq!(fraction.pieces[1].address, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn scripts_are_smaller_and_shifted() {
+        let base = layout("x");
+        let scripts = layout("x^2_i");
+        assert!(scripts.ascent > base.ascent);
+        assert!(scripts.descent > base.descent);
+        assert!(scripts.width < 2.0 * base.width + 0.2);
+    }
+
+    #[test]
+    fn delimiters_grow_with_content() {
+        let small = layout(r"\left( x \right)");
+        let large = layout(r"\left( \frac{1}{x} \right)");
+        assert!(large.ascent + large.descent > small.ascent + small.descent + 0.2);
+        assert!(thick_polyline(&[point(0.0, 0.0), point(1.0, 0.0)], 0.1)
+            .iter()
+            .next()
+            .is_some());
+    }
+}
@@ -0,0 +1,230 @@
+mod layout;
+mod parse;
+
+use super::common::*;
+use super::path::insert_path;
+use crate::{EmptyId, FontFamily, FontId, Fonts, PathId};
+use layout::Typesetter;
+use nannou::lyon::algorithms::aabb::bounding_rect;
+use nannou::lyon::geom::Translation;
+use nannou::lyon::math::Rect;
+use parse::{Expr, Parser};
+use std::ops::Range;
+
+/// Error while parsing a formula, with the byte offset where it occurred.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MathError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl std::fmt::Display for MathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for MathError {}
+
+/// Builder for a formula written in a subset of TeX.
+///
+/// Supported are fractions (`\frac`), square and n-th roots (`\sqrt`,
+/// `\sqrt[n]`), superscripts and subscripts, Greek letters, common binary
+/// operators, relations and arrows, large operators (`\sum`, `\int`, ...),
+/// function names (`\sin`, `\lim`, ...), spacing commands, upright text
+/// (`\text`), and delimiters stretched with `\left ... \right` or sized with
+/// `\big`, `\Big`, `\bigg` and `\Bigg`.
+///
+/// Glyphs come from the fonts of the scene, so symbols missing from the
+/// default font need a fallback font added with [MathBuilder::with_font].
+pub struct MathBuilder<'a> {
+    source: String,
+    expr: Expr,
+    font_size: f32,
+    family: FontFamily,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> MathBuilder<'a> {
+    fn new(scene: &'a mut Scene, source: &str) -> Result<Self, MathError> {
+        Ok(Self {
+            source: source.to_string(),
+            expr: Parser::new(source).parse()?,
+            font_size: 0.4,
+            family: Default::default(),
+            stroke_weight: StrokeWeight::THIN,
+            stroke_color: Color::WHITE,
+            fill_color: Color::WHITE,
+            position: Default::default(),
+            scene,
+        })
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self.fill_color = color;
+        self
+    }
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    /// Use a font registered in the scene's [Fonts](crate::Fonts), e.g. a
+    /// math font covering more symbols. Glyphs missing from it are taken
+    /// from the default font.
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.family = FontFamily::new(font);
+        self
+    }
+    /// Font to use for characters missing from the previously added fonts.
+    pub fn with_fallback_font(mut self, font: FontId) -> Self {
+        self.family.add(font);
+        self
+    }
+
+    /// Create one object per glyph, fraction bar, radical sign and
+    /// delimiter, grouped under an [Empty](crate::Empty) object positioned
+    /// at the center of the formula.
+    pub fn make(&mut self) -> MathId {
+        let fonts = self
+            .scene
+            .world
+            .get_mut()
+            .resource::<Fonts>()
+            .chain(&self.family);
+        let layout = Typesetter::new(&fonts, self.font_size).layout(&self.expr);
+        let pieces: Vec<_> = layout
+            .pieces
+            .into_iter()
+            .filter(|piece| piece.path.iter().next().is_some())
+            .collect();
+        let bounds = pieces
+            .iter()
+            .map(|piece| bounding_rect(piece.path.iter()))
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_else(Rect::zero);
+        let center = bounds.center();
+
+        let mut glyphs = Vec::new();
+        for piece in pieces {
+            let rect = bounding_rect(piece.path.iter());
+            let offset = rect.center();
+            let raw = piece
+                .path
+                .transformed(&Translation::new(-offset.x, -offset.y));
+            let id = insert_path(
+                self.scene,
+                Path::new(raw, true),
+                Position {
+                    x: self.position.x + offset.x - center.x,
+                    y: self.position.y + offset.y - center.y,
+                },
+                Angle::default(),
+                self.stroke_weight,
+                self.stroke_color,
+                Some(self.fill_color),
+            );
+            glyphs.push((piece.address, id));
+        }
+
+        let mut nodes = Vec::new();
+        self.expr.walk(&mut Vec::new(), &mut |address, expr| {
+            nodes.push((address.to_vec(), expr.span.clone()));
+        });
+
+        let mut group = crate::empty(self.scene)
+            .with_position(self.position.x, self.position.y)
+            .with_size(bounds.width(), bounds.height());
+        for (_, id) in glyphs.iter() {
+            group = group.add(*id);
+        }
+        MathId {
+            group: group.make(),
+            source: self.source.clone(),
+            glyphs,
+            nodes,
+        }
+    }
+}
+
+crate::position_builder!(MathBuilder);
+
+pub fn math<'a>(scene: &'a mut Scene, source: &str) -> Result<MathBuilder<'a>, MathError> {
+    MathBuilder::new(scene, source)
+}
+
+/// Formula made of one path object per glyph or drawn symbol.
+///
+/// Sub-expressions are addressed by the indices of the nodes leading to
+/// them in the syntax tree of the formula. At the top level, the formula is
+/// a list of terms, so `&[i]` addresses its `i`-th term. Within a term,
+/// children are numbered as follows:
+///
+/// - a braced group `{...}` lists its terms,
+/// - `\frac{a}{b}` has numerator `0` and denominator `1`,
+/// - `\sqrt[n]{x}` has radicand `0` and index `1`,
+/// - `x^a_b` has base `0`, superscript `1` and subscript `2`,
+/// - `\left( x \right)` has its content as `0`.
+///
+/// For instance in `\frac{a}{b} + \sqrt{x^2}`, the `2` is at `&[2, 0, 0, 1]`.
+#[derive(Debug, Clone)]
+pub struct MathId {
+    group: EmptyId,
+    source: String,
+    /// Objects along with the address of the node drawing them.
+    glyphs: Vec<(Vec<usize>, PathId)>,
+    /// Address and source range of every node of the syntax tree.
+    nodes: Vec<(Vec<usize>, Range<usize>)>,
+}
+
+impl MathId {
+    /// [Empty](crate::Empty) object holding all the glyphs as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    /// Every object of the formula, from left to right within each
+    /// sub-expression.
+    pub fn glyphs(&self) -> Vec<PathId> {
+        self.glyphs.iter().map(|(_, id)| *id).collect()
+    }
+    /// Objects drawing the sub-expression at the given address.
+    pub fn part(&self, address: &[usize]) -> Vec<PathId> {
+        self.glyphs
+            .iter()
+            .filter(|(a, _)| a.starts_with(address))
+            .map(|(_, id)| *id)
+            .collect()
+    }
+    /// Objects drawing the first sub-expression whose source is `tex`,
+    /// ignoring surrounding whitespace and braces.
+    pub fn find(&self, tex: &str) -> Vec<PathId> {
+        let trim = |s: &str| -> String {
+            let s = s.trim();
+            match s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(inner) => inner.trim().to_string(),
+                None => s.to_string(),
+            }
+        };
+        let tex = trim(tex);
+        self.nodes
+            .iter()
+            .find(|(_, span)| trim(&self.source[span.clone()]) == tex)
+            .map(|(address, _)| self.part(address))
+            .unwrap_or_default()
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.glyphs
+            .iter()
+            .map(|(_, id)| id.show_creation())
+            .collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.glyphs.iter().map(|(_, id)| id.fade_in()).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.glyphs.iter().map(|(_, id)| id.fade_out()).collect()
+    }
+}
@@ -0,0 +1,733 @@
+use super::MathError;
+use std::ops::Range;
+
+/// Spacing class of an atom, following the classification of TeX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Class {
+    Ord,
+    /// Large operator, e.g. `\sum`, or function name, e.g. `\sin`.
+    Op,
+    Bin,
+    Rel,
+    Open,
+    Close,
+    Punct,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Kind {
+    /// Single character, drawn in italics for variables.
+    Symbol {
+        ch: char,
+        class: Class,
+        italic: bool,
+    },
+    /// Upright run of characters, e.g. from `\text{...}` or `\sin`, with
+    /// its limits above and below when `limits` is set, e.g. for `\lim`.
+    Word {
+        text: String,
+        class: Class,
+        limits: bool,
+    },
+    /// Large operator drawn bigger than the surrounding text, with its
+    /// limits above and below when `limits` is set.
+    BigOp { ch: char, limits: bool },
+    /// Horizontal space in ems.
+    Space(f32),
+    /// List of sub-expressions laid out side by side.
+    Group,
+    /// Children: numerator and denominator.
+    Frac,
+    /// Children: radicand and index, the latter being empty if absent.
+    Sqrt,
+    /// Children: base, superscript and subscript, the scripts being
+    /// empty groups if absent.
+    Scripts,
+    /// Delimiters stretched around the only child. A delimiter of `.` is
+    /// invisible.
+    Delimited { left: char, right: char },
+    /// Delimiter of fixed size, given as a multiple of the font size.
+    BigDelim { ch: char, size: f32, class: Class },
+}
+
+/// Node of the syntax tree of a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Expr {
+    pub(crate) kind: Kind,
+    pub(crate) children: Vec<Expr>,
+    /// Byte range of the node in the source.
+    pub(crate) span: Range<usize>,
+}
+
+impl Expr {
+    fn leaf(kind: Kind, span: Range<usize>) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+            span,
+        }
+    }
+    fn group(children: Vec<Expr>, span: Range<usize>) -> Self {
+        Self {
+            kind: Kind::Group,
+            children,
+            span,
+        }
+    }
+    /// Every node of the tree along with its address, i.e. the indices of
+    /// the children leading to it from the root.
+    pub(crate) fn walk(&self, address: &mut Vec<usize>, f: &mut impl FnMut(&[usize], &Expr)) {
+        f(address, self);
+        for (i, child) in self.children.iter().enumerate() {
+            address.push(i);
+            child.walk(address, f);
+            address.pop();
+        }
+    }
+}
+
+fn greek(name: &str) -> Option<char> {
+    Some(match name {
+        "alpha" => 'α',
+        "beta" => 'β',
+        "gamma" => 'γ',
+        "delta" => 'δ',
+        "epsilon" => 'ϵ',
+        "varepsilon" => 'ε',
+        "zeta" => 'ζ',
+        "eta" => 'η',
+        "theta" => 'θ',
+        "vartheta" => 'ϑ',
+        "iota" => 'ι',
+        "kappa" => 'κ',
+        "lambda" => 'λ',
+        "mu" => 'μ',
+        "nu" => 'ν',
+        "xi" => 'ξ',
+        "pi" => 'π',
+        "varpi" => 'ϖ',
+        "rho" => 'ρ',
+        "varrho" => 'ϱ',
+        "sigma" => 'σ',
+        "varsigma" => 'ς',
+        "tau" => 'τ',
+        "upsilon" => 'υ',
+        "phi" => 'ϕ',
+        "varphi" => 'φ',
+        "chi" => 'χ',
+        "psi" => 'ψ',
+        "omega" => 'ω',
+        "Gamma" => 'Γ',
+        "Delta" => 'Δ',
+        "Theta" => 'Θ',
+        "Lambda" => 'Λ',
+        "Xi" => 'Ξ',
+        "Pi" => 'Π',
+        "Sigma" => 'Σ',
+        "Upsilon" => 'Υ',
+        "Phi" => 'Φ',
+        "Psi" => 'Ψ',
+        "Omega" => 'Ω',
+        _ => return None,
+    })
+}
+
+fn symbol(name: &str) -> Option<(char, Class)> {
+    use Class::*;
+    Some(match name {
+        "times" => ('×', Bin),
+        "div" => ('÷', Bin),
+        "pm" => ('±', Bin),
+        "mp" => ('∓', Bin),
+        "cdot" => ('·', Bin),
+        "ast" => ('∗', Bin),
+        "circ" => ('∘', Bin),
+        "cup" => ('∪', Bin),
+        "cap" => ('∩', Bin),
+        "leq" | "le" => ('≤', Rel),
+        "geq" | "ge" => ('≥', Rel),
+        "neq" | "ne" => ('≠', Rel),
+        "approx" => ('≈', Rel),
+        "equiv" => ('≡', Rel),
+        "sim" => ('∼', Rel),
+        "propto" => ('∝', Rel),
+        "in" => ('∈', Rel),
+        "notin" => ('∉', Rel),
+        "subset" => ('⊂', Rel),
+        "supset" => ('⊃', Rel),
+        "subseteq" => ('⊆', Rel),
+        "to" | "rightarrow" => ('→', Rel),
+        "leftarrow" | "gets" => ('←', Rel),
+        "Rightarrow" | "implies" => ('⇒', Rel),
+        "Leftrightarrow" | "iff" => ('⇔', Rel),
+        "mapsto" => ('↦', Rel),
+        "infty" => ('∞', Ord),
+        "partial" => ('∂', Ord),
+        "nabla" => ('∇', Ord),
+        "forall" => ('∀', Ord),
+        "exists" => ('∃', Ord),
+        "ldots" | "dots" => ('…', Ord),
+        "cdots" => ('⋯', Ord),
+        "prime" => ('′', Ord),
+        "hbar" => ('ħ', Ord),
+        "ell" => ('ℓ', Ord),
+        "emptyset" => ('∅', Ord),
+        "angle" => ('∠', Ord),
+        "degree" => ('°', Ord),
+        "{" | "lbrace" => ('{', Open),
+        "}" | "rbrace" => ('}', Close),
+        "langle" => ('⟨', Open),
+        "rangle" => ('⟩', Close),
+        "|" => ('‖', Ord),
+        "%" => ('%', Ord),
+        "$" => ('$', Ord),
+        "&" => ('&', Ord),
+        "#" => ('#', Ord),
+        "_" => ('_', Ord),
+        _ => return None,
+    })
+}
+
+fn big_operator(name: &str) -> Option<(char, bool)> {
+    Some(match name {
+        "sum" => ('∑', true),
+        "prod" => ('∏', true),
+        "coprod" => ('∐', true),
+        "bigcup" => ('⋃', true),
+        "bigcap" => ('⋂', true),
+        "int" => ('∫', false),
+        "iint" => ('∬', false),
+        "oint" => ('∮', false),
+        _ => return None,
+    })
+}
+
+/// Function names typeset upright, with whether they take their limits
+/// above and below.
+fn function(name: &str) -> Option<bool> {
+    match name {
+        "sin" | "cos" | "tan" | "cot" | "sec" | "csc" | "arcsin" | "arccos" | "arctan" | "sinh"
+        | "cosh" | "tanh" | "log" | "ln" | "exp" | "deg" | "det" | "dim" | "ker" | "arg"
+        | "gcd" | "Pr" => Some(false),
+        "lim" | "max" | "min" | "sup" | "inf" | "limsup" | "liminf" => Some(true),
+        _ => None,
+    }
+}
+
+fn space(name: &str) -> Option<f32> {
+    Some(match name {
+        "," => 3.0 / 18.0,
+        ":" | ">" => 4.0 / 18.0,
+        ";" => 5.0 / 18.0,
+        "!" => -3.0 / 18.0,
+        " " => 6.0 / 18.0,
+        "quad" => 1.0,
+        "qquad" => 2.0,
+        _ => return None,
+    })
+}
+
+fn big_delimiter_size(name: &str) -> Option<(f32, Class)> {
+    use Class::*;
+    Some(match name {
+        "big" => (1.2, Ord),
+        "Big" => (1.8, Ord),
+        "bigg" => (2.4, Ord),
+        "Bigg" => (3.0, Ord),
+        "bigl" => (1.2, Open),
+        "Bigl" => (1.8, Open),
+        "biggl" => (2.4, Open),
+        "Biggl" => (3.0, Open),
+        "bigr" => (1.2, Close),
+        "Bigr" => (1.8, Close),
+        "biggr" => (2.4, Close),
+        "Biggr" => (3.0, Close),
+        _ => return None,
+    })
+}
+
+fn char_class(ch: char) -> Class {
+    match ch {
+        '+' | '-' | '*' => Class::Bin,
+        '=' | '<' | '>' | ':' => Class::Rel,
+        '(' | '[' => Class::Open,
+        ')' | ']' => Class::Close,
+        ',' | ';' => Class::Punct,
+        _ => Class::Ord,
+    }
+}
+
+/// Recursive descent parser for a subset of TeX math.
+pub(crate) struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    /// Parse the whole source into a group.
+    pub(crate) fn parse(mut self) -> Result<Expr, MathError> {
+        let children = self.list(None)?;
+        if self.pos < self.source.len() {
+            return Err(self.error("unexpected '}'"));
+        }
+        Ok(Expr::group(children, 0..self.source.len()))
+    }
+
+    fn error(&self, message: impl Into<String>) -> MathError {
+        MathError {
+            message: message.into(),
+            position: self.pos,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Whether the source continues with the given control sequence.
+    fn at_command(&self, name: &str) -> bool {
+        let rest = &self.source[self.pos..];
+        rest.strip_prefix('\\')
+            .and_then(|rest| rest.strip_prefix(name))
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_alphabetic()))
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), MathError> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some(ch) if ch == expected => Ok(()),
+            _ => Err(self.error(format!("expected '{}'", expected))),
+        }
+    }
+
+    /// Name of a control sequence, after the backslash.
+    fn command_name(&mut self) -> Result<String, MathError> {
+        let start = self.pos;
+        match self.bump() {
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                    self.bump();
+                }
+                Ok(self.source[start..self.pos].to_string())
+            }
+            Some(ch) => Ok(ch.to_string()),
+            None => Err(self.error("expected command name after '\\'")),
+        }
+    }
+
+    /// Sequence of atoms until the end of the source, a closing brace, or
+    /// the `\right` command given as `terminator`.
+    fn list(&mut self, terminator: Option<&str>) -> Result<Vec<Expr>, MathError> {
+        let mut items: Vec<Expr> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.peek() {
+                None | Some('}') => {
+                    if let Some(terminator) = terminator {
+                        return Err(self.error(format!("missing \\{}", terminator)));
+                    }
+                    return Ok(items);
+                }
+                Some('^') | Some('_') => {
+                    let base = match items.pop() {
+                        Some(base) if base.kind != Kind::Scripts => base,
+                        Some(base) => {
+                            // Add the other script to the existing ones.
+                            let script = self.script()?;
+                            items.push(self.add_script(base, script)?);
+                            continue;
+                        }
+                        None => Expr::group(Vec::new(), start..start),
+                    };
+                    let empty = Expr::group(Vec::new(), start..start);
+                    let scripts = Expr {
+                        span: base.span.clone(),
+                        kind: Kind::Scripts,
+                        children: vec![base, empty.clone(), empty],
+                    };
+                    let script = self.script()?;
+                    items.push(self.add_script(scripts, script)?);
+                }
+                Some('\\') if terminator.is_some() && self.at_command("right") => {
+                    return Ok(items);
+                }
+                Some('\'') => {
+                    self.bump();
+                    let prime = Expr::leaf(
+                        Kind::Symbol {
+                            ch: '′',
+                            class: Class::Ord,
+                            italic: false,
+                        },
+                        start..self.pos,
+                    );
+                    let base = items
+                        .pop()
+                        .unwrap_or_else(|| Expr::group(Vec::new(), start..start));
+                    if base.kind == Kind::Scripts && self.takes_primes(&base.children[1]) {
+                        // Further primes join the superscript, as in `x_1''`.
+                        items.push(self.extend_superscript(base, vec![prime], self.pos));
+                        continue;
+                    }
+                    let empty = Expr::group(Vec::new(), start..start);
+                    let span = base.span.start..self.pos;
+                    items.push(Expr {
+                        kind: Kind::Scripts,
+                        children: vec![base, Expr::group(vec![prime], start..self.pos), empty],
+                        span,
+                    });
+                }
+                Some(_) => items.push(self.atom()?),
+            }
+        }
+    }
+
+    /// Superscript or subscript, as `(is_superscript, argument)`.
+    fn script(&mut self) -> Result<(bool, Expr), MathError> {
+        let superscript = self.bump() == Some('^');
+        Ok((superscript, self.argument()?))
+    }
+
+    fn add_script(
+        &self,
+        mut scripts: Expr,
+        (superscript, script): (bool, Expr),
+    ) -> Result<Expr, MathError> {
+        if superscript && self.is_primes(&scripts.children[1]) {
+            // A superscript after primes follows them, as in `x'^2`.
+            let end = script.span.end;
+            let children = match script.kind {
+                Kind::Group => script.children,
+                _ => vec![script],
+            };
+            return Ok(self.extend_superscript(scripts, children, end));
+        }
+        let index = if superscript { 1 } else { 2 };
+        let slot = &mut scripts.children[index];
+        if !slot.children.is_empty() || slot.span.start != slot.span.end {
+            return Err(self.error(if superscript {
+                "double superscript"
+            } else {
+                "double subscript"
+            }));
+        }
+        scripts.span.end = scripts.span.end.max(script.span.end);
+        *slot = match script.kind {
+            Kind::Group => script,
+            _ => {
+                let span = script.span.clone();
+                Expr::group(vec![script], span)
+            }
+        };
+        Ok(scripts)
+    }
+
+    /// Whether the superscript `slot` holds only primes written as `'`.
+    fn is_primes(&self, slot: &Expr) -> bool {
+        !slot.children.is_empty()
+            && slot
+                .children
+                .iter()
+                .all(|child| &self.source[child.span.clone()] == "'")
+    }
+
+    /// Whether primes can be added to the superscript `slot`, i.e. it is
+    /// empty or holds only primes.
+    fn takes_primes(&self, slot: &Expr) -> bool {
+        (slot.children.is_empty() && slot.span.start == slot.span.end) || self.is_primes(slot)
+    }
+
+    /// Append `children` to the superscript of `scripts`, which then ends
+    /// at byte `end`.
+    fn extend_superscript(&self, mut scripts: Expr, children: Vec<Expr>, end: usize) -> Expr {
+        let slot = &mut scripts.children[1];
+        if slot.children.is_empty() {
+            slot.span = children.first().map_or(end, |c| c.span.start)..end;
+        }
+        slot.span.end = slot.span.end.max(end);
+        slot.children.extend(children);
+        scripts.span.end = scripts.span.end.max(end);
+        scripts
+    }
+
+    /// Argument of a command or script: a braced group or a single atom.
+    fn argument(&mut self) -> Result<Expr, MathError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                let children = self.list(None)?;
+                self.expect('}')?;
+                Ok(Expr::group(children, start..self.pos))
+            }
+            None | Some('}') => Err(self.error("missing argument")),
+            Some(_) => {
+                let atom = self.atom()?;
+                let span = atom.span.clone();
+                Ok(Expr::group(vec![atom], span))
+            }
+        }
+    }
+
+    /// Raw text between braces, for `\text{...}`.
+    fn text_argument(&mut self) -> Result<String, MathError> {
+        self.expect('{')?;
+        let start = self.pos;
+        let mut depth = 0;
+        loop {
+            match self.bump() {
+                Some('{') => depth += 1,
+                Some('}') if depth == 0 => return Ok(self.source[start..self.pos - 1].to_string()),
+                Some('}') => depth -= 1,
+                Some(_) => {}
+                None => return Err(self.error("missing '}'")),
+            }
+        }
+    }
+
+    /// Delimiter following `\left`, `\right` or `\big`.
+    fn delimiter(&mut self) -> Result<char, MathError> {
+        self.skip_whitespace();
+        match self.bump() {
+            Some('\\') => {
+                let name = self.command_name()?;
+                match name.as_str() {
+                    "{" | "lbrace" => Ok('{'),
+                    "}" | "rbrace" => Ok('}'),
+                    "langle" => Ok('⟨'),
+                    "rangle" => Ok('⟩'),
+                    "|" | "Vert" => Ok('‖'),
+                    "vert" => Ok('|'),
+                    _ => Err(self.error(format!("unknown delimiter \\{}", name))),
+                }
+            }
+            Some(ch) if "()[]|./<>".contains(ch) => Ok(match ch {
+                '<' => '⟨',
+                '>' => '⟩',
+                ch => ch,
+            }),
+            _ => Err(self.error("expected delimiter")),
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expr, MathError> {
+        let start = self.pos;
+        let ch = self.bump().ok_or_else(|| self.error("unexpected end"))?;
+        let kind = match ch {
+            '{' => {
+                let children = self.list(None)?;
+                self.expect('}')?;
+                return Ok(Expr::group(children, start..self.pos));
+            }
+            '\\' => return self.command(start),
+            '-' => Kind::Symbol {
+                ch: '−',
+                class: Class::Bin,
+                italic: false,
+            },
+            ch => Kind::Symbol {
+                ch,
+                class: char_class(ch),
+                italic: ch.is_alphabetic(),
+            },
+        };
+        Ok(Expr::leaf(kind, start..self.pos))
+    }
+
+    fn command(&mut self, start: usize) -> Result<Expr, MathError> {
+        let name = self.command_name()?;
+        let leaf = |kind: Kind, this: &Self| Ok(Expr::leaf(kind, start..this.pos));
+        if let Some(ch) = greek(&name) {
+            return leaf(
+                Kind::Symbol {
+                    ch,
+                    class: Class::Ord,
+                    italic: ch.is_lowercase(),
+                },
+                self,
+            );
+        }
+        if let Some((ch, class)) = symbol(&name) {
+            return leaf(
+                Kind::Symbol {
+                    ch,
+                    class,
+                    italic: false,
+                },
+                self,
+            );
+        }
+        if let Some((ch, limits)) = big_operator(&name) {
+            return leaf(Kind::BigOp { ch, limits }, self);
+        }
+        if let Some(limits) = function(&name) {
+            return leaf(
+                Kind::Word {
+                    text: name,
+                    class: Class::Op,
+                    limits,
+                },
+                self,
+            );
+        }
+        if let Some(em) = space(&name) {
+            return leaf(Kind::Space(em), self);
+        }
+        if let Some((size, class)) = big_delimiter_size(&name) {
+            let ch = self.delimiter()?;
+            return leaf(Kind::BigDelim { ch, size, class }, self);
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.argument()?;
+                let denominator = self.argument()?;
+                Ok(Expr {
+                    kind: Kind::Frac,
+                    children: vec![numerator, denominator],
+                    span: start..self.pos,
+                })
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                let index_start = self.pos;
+                let index = if self.peek() == Some('[') {
+                    self.bump();
+                    let mut children = Vec::new();
+                    loop {
+                        self.skip_whitespace();
+                        match self.peek() {
+                            Some(']') => break,
+                            None => return Err(self.error("missing ']'")),
+                            Some(_) => children.push(self.atom()?),
+                        }
+                    }
+                    self.bump();
+                    Expr::group(children, index_start..self.pos)
+                } else {
+                    Expr::group(Vec::new(), index_start..index_start)
+                };
+                let radicand = self.argument()?;
+                Ok(Expr {
+                    kind: Kind::Sqrt,
+                    children: vec![radicand, index],
+                    span: start..self.pos,
+                })
+            }
+            "left" => {
+                let left = self.delimiter()?;
+                let body_start = self.pos;
+                let children = self.list(Some("right"))?;
+                let body = Expr::group(children, body_start..self.pos);
+                self.pos += "\\right".len();
+                let right = self.delimiter()?;
+                Ok(Expr {
+                    kind: Kind::Delimited { left, right },
+                    children: vec![body],
+                    span: start..self.pos,
+                })
+            }
+            "right" => Err(self.error("\\right without matching \\left")),
+            "text" | "mathrm" | "operatorname" => {
+                let text = self.text_argument()?;
+                let class = if name == "operatorname" {
+                    Class::Op
+                } else {
+                    Class::Ord
+                };
+                leaf(
+                    Kind::Word {
+                        text,
+                        class,
+                        limits: false,
+                    },
+                    self,
+                )
+            }
+            _ => Err(MathError {
+                message: format!("unknown command \\{}", name),
+                position: start,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Expr {
+        Parser::new(source).parse().unwrap()
+    }
+
+    #[test]
+    fn parses_nested_structures() {
+        let expr = parse(r"\frac{a}{b} + \sqrt{x^2}");
+        assert_eq!(expr.children.len(), 3);
+        assert_eq!(expr.children[0].kind, Kind::Frac);
+        assert_eq!(expr.children[2].kind, Kind::Sqrt);
+        let radicand = &expr.children[2].children[0];
+        assert_eq!(radicand.children[0].kind, Kind::Scripts);
+        assert_eq!(&r"\frac{a}{b} + \sqrt{x^2}"[radicand.span.clone()], "{x^2}");
+
+        let expr = parse(r"x_i^2 \leq \left( \alpha \right)");
+        let scripts = &expr.children[0];
+        assert_eq!(scripts.children[1].children.len(), 1);
+        assert_eq!(scripts.children[2].children.len(), 1);
+        assert_eq!(
+            expr.children[2].kind,
+            Kind::Delimited {
+                left: '(',
+                right: ')'
+            }
+        );
+    }
+
+    #[test]
+    fn primes_join_superscript() {
+        let source = r"x'^2 + y_1''";
+        let expr = parse(source);
+        assert_eq!(expr.children.len(), 3);
+        let scripts = &expr.children[0];
+        assert_eq!(scripts.kind, Kind::Scripts);
+        assert_eq!(scripts.children[1].children.len(), 2);
+        assert_eq!(&source[scripts.span.clone()], "x'^2");
+        let scripts = &expr.children[2];
+        assert_ne!(scripts.children[0].kind, Kind::Scripts);
+        assert_eq!(scripts.children[1].children.len(), 2);
+        assert_eq!(scripts.children[2].children.len(), 1);
+        assert_eq!(&source[scripts.children[1].span.clone()], "''");
+
+        let source = r"f'^{(n)}";
+        let scripts = &parse(source).children[0];
+        assert_eq!(scripts.children[1].children.len(), 4);
+        assert_eq!(&source[scripts.span.clone()], source);
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert!(Parser::new(r"\frac{a}").parse().is_err());
+        assert!(Parser::new(r"x^2^3").parse().is_err());
+        assert!(Parser::new(r"x'^2^3").parse().is_err());
+        assert!(Parser::new(r"\left( x").parse().is_err());
+        let error = Parser::new(r"a + \foo").parse().unwrap_err();
+        assert_eq!(error.position, 4);
+    }
+}
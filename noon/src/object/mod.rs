@@ -2,20 +2,51 @@
 
 use bevy_ecs::prelude::*;
 
+pub mod arc;
 pub mod arrow;
+pub mod axes;
+pub mod brace;
 pub mod circle;
+pub mod code;
 pub mod dot;
+pub mod ellipse;
 pub mod empty;
+pub mod graph;
 pub mod line;
+pub mod math;
+pub mod number_line;
+pub mod number_plane;
+pub mod path;
+pub mod polygon;
 pub mod rectangle;
+pub mod svg;
+pub mod table;
 pub mod text;
 pub mod triangle;
+pub mod vector_field;
 
+pub use arc::*;
+pub use arrow::*;
+pub use axes::*;
+pub use brace::*;
 pub use circle::*;
+pub use code::*;
+pub use dot::*;
+pub use ellipse::*;
 pub use empty::*;
+pub use graph::*;
 pub use line::*;
+pub use math::*;
+pub use number_line::*;
+pub use number_plane::*;
+pub use path::*;
+pub use polygon::*;
 pub use rectangle::*;
+pub use svg::*;
+pub use table::*;
 pub use text::*;
+pub use triangle::*;
+pub use vector_field::*;
 
 use crate::{Animation, Color, EntityAnimations, FillColor, Opacity, Position, Size, StrokeColor};
 
@@ -24,29 +55,21 @@ mod common {
     pub use crate::PixelFrame;
     pub use crate::{
         Angle, AnimBuilder, Animation, BoundingSize, Color, ColorExtension, Create, Depth,
-        EaseType, EntityAnimations, FillColor, FontSize, HasFill, Opacity, Path, PathCompletion,
-        PathComponent, PixelPath, Point, Position, Previous, Scale, Scene, Size, StrokeColor,
-        StrokeWeight, Transform, Value, WithAngle, WithColor, WithFill, WithFontSize, WithId,
-        WithPath, WithPosition, WithSize, WithStroke, WithStrokeWeight, EPS, TO_PXL,
+        EaseType, EntityAnimations, FillColor, FontSize, HasFill, InnerRadius, Opacity, Path,
+        PathCompletion, PathComponent, PixelPath, Point, Position, Previous, Scale, Scene, Size,
+        StartAngle, StrokeColor, StrokeWeight, SweepAngle, Transform, Value, WithAngle, WithArc,
+        WithColor, WithFill, WithFontSize, WithId, WithInnerRadius, WithPath, WithPosition,
+        WithSize, WithStroke, WithStrokeWeight, EPS, TO_PXL,
     };
     pub use bevy_ecs::prelude::*;
     pub use nannou::color::Rgba;
     pub use nannou::lyon::math::point;
 }
 
-#[derive(Component)]
-pub struct Triangle;
-
-#[derive(Component)]
-pub struct Arrow;
-
-#[derive(Component)]
-pub struct Dot;
-
 #[macro_export]
 macro_rules! stroke_builder {
-    ($name:ident) => {
-        impl<'a> $name<'a> {
+    ($name:ident $(, $param:ident)?) => {
+        impl<'a $(, $param)?> $name<'a $(, $param)?> {
             pub fn with_stroke_weight(mut self, weight: f32) -> Self {
                 self.stroke_weight = StrokeWeight(weight);
                 self
@@ -69,8 +92,8 @@ macro_rules! stroke_builder {
 
 #[macro_export]
 macro_rules! position_builder {
-    ($name:ident) => {
-        impl<'a> $name<'a> {
+    ($name:ident $(, $param:ident)?) => {
+        impl<'a $(, $param)?> $name<'a $(, $param)?> {
             pub fn with_position(mut self, x: f32, y: f32) -> Self {
                 self.position = Position { x, y };
                 self
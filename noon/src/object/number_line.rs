@@ -0,0 +1,589 @@
+use super::axes::{format_number, place_label, tick_indices};
+use super::common::*;
+use crate::component::Children;
+use crate::{DotId, EmptyId, LineId, TextId, DOWN};
+use std::collections::btree_map::{BTreeMap, Entry};
+use std::ops::Range;
+
+/// Most ticks shown at once. Ranges which would show more put ticks at a
+/// multiple of the tick step instead.
+const MAX_TICKS: i64 = 50;
+
+/// Error for a number line range which is empty, reversed or not finite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberLineError {
+    pub range: Range<f32>,
+}
+
+impl std::fmt::Display for NumberLineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "number line range {:?} should be finite and increasing",
+            self.range
+        )
+    }
+}
+
+impl std::error::Error for NumberLineError {}
+
+fn check_range(range: &Range<f32>) -> Result<(), NumberLineError> {
+    if range.start.is_finite() && range.end.is_finite() && range.end > range.start {
+        Ok(())
+    } else {
+        Err(NumberLineError {
+            range: range.clone(),
+        })
+    }
+}
+
+/// Multiple of `step` between the ticks shown over `range`, the first of
+/// 1, 2, 5, 10, 20, 50, ... giving at most [MAX_TICKS] ticks.
+fn tick_factor(range: &Range<f32>, step: f32) -> i64 {
+    let mut base: i64 = 1;
+    loop {
+        for factor in [base, 2 * base, 5 * base] {
+            let indices = tick_indices(range, step * factor as f32);
+            if indices.end.saturating_sub(indices.start) <= MAX_TICKS {
+                return factor;
+            }
+        }
+        match base.checked_mul(10) {
+            Some(next) => base = next,
+            None => return base,
+        }
+    }
+}
+
+/// Indices of the ticks shown over `range`, as multiples of `step`.
+fn visible_ticks(range: &Range<f32>, step: f32) -> Vec<i64> {
+    let factor = tick_factor(range, step);
+    tick_indices(range, step * factor as f32)
+        .map(|i| i * factor)
+        .collect()
+}
+
+/// Builder for a horizontal number line with tick marks and numeric labels.
+pub struct NumberLineBuilder<'a> {
+    range: Range<f32>,
+    unit: f32,
+    step: f32,
+    labels: bool,
+    pointer: Option<f32>,
+    font_size: f32,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    pointer_color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> NumberLineBuilder<'a> {
+    fn new(scene: &'a mut Scene, range: Range<f32>) -> Self {
+        Self {
+            range,
+            unit: 1.0,
+            step: 1.0,
+            labels: true,
+            pointer: None,
+            font_size: 0.2,
+            stroke_weight: StrokeWeight(2.0),
+            stroke_color: Color::WHITE,
+            pointer_color: Color::random(),
+            position: Default::default(),
+            scene,
+        }
+    }
+    /// Length in scene units of one unit on the number line.
+    pub fn with_unit_length(mut self, length: f32) -> Self {
+        self.unit = length;
+        self
+    }
+    /// Distance between tick marks in units of the number line. Ranges that
+    /// would show more than 50 ticks use a multiple of it.
+    pub fn with_tick_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+    pub fn without_labels(mut self) -> Self {
+        self.labels = false;
+        self
+    }
+    /// Add a dot marking the given value, which can then be animated with
+    /// [NumberLineId::set_value].
+    pub fn with_pointer(mut self, value: f32) -> Self {
+        self.pointer = Some(value);
+        self
+    }
+    pub fn with_pointer_color(mut self, color: Color) -> Self {
+        self.pointer_color = color;
+        self
+    }
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+
+    pub fn make(&mut self) -> NumberLineId {
+        let center = point(self.position.x, self.position.y);
+        let length = (self.range.end - self.range.start) * self.unit;
+        let line = crate::line(self.scene)
+            .from(center.x - length / 2.0, center.y)
+            .to(center.x + length / 2.0, center.y)
+            .with_color(self.stroke_color)
+            .with_stroke_weight(self.stroke_weight.0)
+            .make();
+        let group = crate::empty(self.scene)
+            .add(line)
+            .with_position(center.x, center.y)
+            .make();
+
+        let mut number_line = NumberLineId {
+            group,
+            line,
+            pointer: None,
+            center,
+            length,
+            step: self.step,
+            labels: self.labels,
+            font_size: self.font_size,
+            stroke_weight: self.stroke_weight,
+            stroke_color: self.stroke_color,
+        };
+        let mut state = NumberLineState {
+            range: self.range.clone(),
+            value: self.pointer.unwrap_or_default(),
+            pointer_visible: false,
+            ticks: BTreeMap::new(),
+        };
+
+        for index in visible_ticks(&self.range, self.step) {
+            let tick = number_line.make_tick(self.scene, &self.range, index);
+            number_line.adopt(self.scene, tick.entities());
+            state.ticks.insert(index, tick);
+        }
+        if let Some(value) = self.pointer {
+            let p = number_line.point_at(&self.range, value);
+            let pointer = crate::dot(self.scene)
+                .with_color(self.pointer_color)
+                .with_position(p.x, p.y)
+                .make();
+            number_line.adopt(self.scene, [pointer.0]);
+            number_line.pointer = Some(pointer);
+            state.pointer_visible = state.contains(value);
+        }
+        self.scene.world.get_mut().entity_mut(group.0).insert(state);
+        number_line
+    }
+}
+
+crate::stroke_builder!(NumberLineBuilder);
+crate::position_builder!(NumberLineBuilder);
+
+/// Number line showing `range`, which should be finite and increasing.
+pub fn number_line(
+    scene: &mut Scene,
+    range: Range<f32>,
+) -> Result<NumberLineBuilder<'_>, NumberLineError> {
+    check_range(&range)?;
+    Ok(NumberLineBuilder::new(scene, range))
+}
+
+/// Tick mark of a [NumberLineId], with its label.
+#[derive(Debug, Clone, Copy)]
+pub struct Tick {
+    pub line: LineId,
+    pub label: Option<TextId>,
+    visible: bool,
+}
+
+/// Range, pointer value and ticks of a number line, stored on its group so
+/// that every copy of a [NumberLineId] sees the same state.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct NumberLineState {
+    range: Range<f32>,
+    /// Value marked by the pointer.
+    value: f32,
+    /// Whether the pointer is shown, i.e. its value is within the range.
+    pointer_visible: bool,
+    /// Ticks created so far, indexed by multiple of the tick step.
+    ticks: BTreeMap<i64, Tick>,
+}
+
+impl NumberLineState {
+    fn contains(&self, value: f32) -> bool {
+        self.range.start - EPS <= value && value <= self.range.end + EPS
+    }
+}
+
+/// Number line made of several objects.
+///
+/// The visible range can be animated with [NumberLineId::set_range], in which
+/// case the ticks slide to their new place, and ticks entering or leaving the
+/// range fade in or out.
+///
+/// The range, the pointer value and the ticks live in the scene, so copies of
+/// the id stay in sync. They change as soon as [set_range](NumberLineId::set_range)
+/// or [set_value](NumberLineId::set_value) is called, before the returned
+/// animations play.
+#[derive(Debug, Clone)]
+pub struct NumberLineId {
+    group: EmptyId,
+    line: LineId,
+    pointer: Option<DotId>,
+    center: Point,
+    length: f32,
+    step: f32,
+    labels: bool,
+    font_size: f32,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+}
+
+impl NumberLineId {
+    /// [Empty](crate::Empty) object holding the line, ticks, labels and
+    /// pointer as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn line(&self) -> LineId {
+        self.line
+    }
+    pub fn pointer(&self) -> Option<DotId> {
+        self.pointer
+    }
+    /// Ticks within the current range, in increasing order.
+    pub fn ticks(&self, scene: &Scene) -> Vec<Tick> {
+        self.state(scene)
+            .ticks
+            .values()
+            .filter(|t| t.visible)
+            .copied()
+            .collect()
+    }
+    pub fn range(&self, scene: &Scene) -> Range<f32> {
+        self.state(scene).range
+    }
+    /// Value marked by the pointer.
+    pub fn value(&self, scene: &Scene) -> f32 {
+        self.state(scene).value
+    }
+    pub fn number_to_point(&self, scene: &Scene, number: f32) -> Point {
+        self.point_at(&self.state(scene).range, number)
+    }
+    pub fn point_to_number(&self, scene: &Scene, p: Point) -> f32 {
+        let range = self.state(scene).range;
+        let unit = self.length / (range.end - range.start);
+        range.start + (p.x - self.center.x + self.length / 2.0) / unit
+    }
+
+    fn state(&self, scene: &Scene) -> NumberLineState {
+        scene
+            .world
+            .borrow()
+            .get::<NumberLineState>(self.group.0)
+            .cloned()
+            .expect("number line should be in the scene")
+    }
+
+    fn set_state(&self, scene: &mut Scene, state: NumberLineState) {
+        if let Some(mut current) = scene
+            .world
+            .get_mut()
+            .get_mut::<NumberLineState>(self.group.0)
+        {
+            *current = state;
+        }
+    }
+
+    fn point_at(&self, range: &Range<f32>, number: f32) -> Point {
+        number_to_point(range, self.center, self.length, number)
+    }
+
+    fn adopt(&self, scene: &mut Scene, entities: impl IntoIterator<Item = Entity>) {
+        if let Some(mut children) = scene.world.get_mut().get_mut::<Children>(self.group.0) {
+            for entity in entities {
+                children.add(entity);
+            }
+        }
+    }
+
+    fn make_tick(&self, scene: &mut Scene, range: &Range<f32>, index: i64) -> Tick {
+        let value = index as f32 * self.step;
+        let p = self.point_at(range, value);
+        let size = 0.06;
+        let line = crate::line(scene)
+            .from(p.x, p.y - size)
+            .to(p.x, p.y + size)
+            .with_color(self.stroke_color)
+            .with_stroke_weight(self.stroke_weight.0)
+            .make();
+        let label = self.labels.then(|| {
+            place_label(
+                scene,
+                &format_number(value),
+                self.font_size,
+                self.stroke_color,
+                point(p.x, p.y - size - 0.1),
+                DOWN,
+            )
+        });
+        Tick {
+            line,
+            label,
+            visible: true,
+        }
+    }
+
+    /// Animate the pointer to the given value. The pointer fades out while
+    /// the value is outside the range, and back in once it returns.
+    pub fn set_value(&self, scene: &mut Scene, value: f32) -> Vec<EntityAnimations> {
+        let mut state = self.state(scene);
+        let animations = self.move_pointer(&mut state, value);
+        self.set_state(scene, state);
+        animations
+    }
+
+    fn move_pointer(&self, state: &mut NumberLineState, value: f32) -> Vec<EntityAnimations> {
+        state.value = value;
+        let p = self.point_at(&state.range, value);
+        let visible = state.contains(value);
+        let was_visible = std::mem::replace(&mut state.pointer_visible, visible);
+        self.pointer
+            .iter()
+            .map(|pointer| {
+                let mut animations = pointer.move_to(p.x, p.y);
+                if visible != was_visible {
+                    let fade = if visible {
+                        pointer.fade_in()
+                    } else {
+                        pointer.fade_out()
+                    };
+                    animations.animations.extend(fade.animations);
+                }
+                animations
+            })
+            .collect()
+    }
+
+    /// Animate the range shown by the number line, keeping its length.
+    /// Ticks are added or removed as needed, using a multiple of the tick
+    /// step when the range would show more than 50 ticks.
+    pub fn set_range(
+        &self,
+        scene: &mut Scene,
+        range: Range<f32>,
+    ) -> Result<Vec<EntityAnimations>, NumberLineError> {
+        check_range(&range)?;
+        let mut state = self.state(scene);
+        let old_range = std::mem::replace(&mut state.range, range.clone());
+
+        let mut animations = Vec::new();
+        let indices = visible_ticks(&range, self.step);
+        for index in indices.iter().copied() {
+            if let Entry::Vacant(entry) = state.ticks.entry(index) {
+                // New ticks start from where they would have been before the change.
+                let value = index as f32 * self.step;
+                let start = self.point_at(&old_range, value);
+                let mut tick = self.make_tick(scene, &range, index);
+                tick.visible = false;
+                let world = scene.world.get_mut();
+                for entity in tick.entities() {
+                    if let Some(mut position) = world.get_mut::<Position>(entity) {
+                        position.x += start.x - self.point_at(&range, value).x;
+                    }
+                }
+                self.adopt(scene, tick.entities());
+                entry.insert(tick);
+            }
+        }
+
+        for (index, tick) in state.ticks.iter_mut() {
+            let visible = indices.binary_search(index).is_ok();
+            if !visible && !tick.visible {
+                continue;
+            }
+            let value = *index as f32 * self.step;
+            let shift = self.point_at(&range, value).x - self.point_at(&old_range, value).x;
+            for entity in tick.entities() {
+                let mut entity_animations = if visible && !tick.visible {
+                    EntityAnimations::fade_in(entity)
+                } else if !visible && tick.visible {
+                    EntityAnimations::fade_out(entity)
+                } else {
+                    EntityAnimations {
+                        entity,
+                        animations: Vec::new(),
+                    }
+                };
+                entity_animations
+                    .animations
+                    .push(Animation::by(Position { x: shift, y: 0.0 }).into());
+                animations.push(entity_animations);
+            }
+            tick.visible = visible;
+        }
+
+        let value = state.value;
+        animations.extend(self.move_pointer(&mut state, value));
+        self.set_state(scene, state);
+        Ok(animations)
+    }
+
+    pub fn show_creation(&self, scene: &Scene) -> Vec<EntityAnimations> {
+        self.entities(scene)
+            .into_iter()
+            .map(EntityAnimations::show_creation)
+            .collect()
+    }
+    pub fn fade_in(&self, scene: &Scene) -> Vec<EntityAnimations> {
+        self.entities(scene)
+            .into_iter()
+            .map(EntityAnimations::fade_in)
+            .collect()
+    }
+    pub fn fade_out(&self, scene: &Scene) -> Vec<EntityAnimations> {
+        self.entities(scene)
+            .into_iter()
+            .map(EntityAnimations::fade_out)
+            .collect()
+    }
+
+    /// Line, visible ticks and pointer.
+    fn entities(&self, scene: &Scene) -> Vec<Entity> {
+        let state = self.state(scene);
+        std::iter::once(self.line.0)
+            .chain(
+                state
+                    .ticks
+                    .values()
+                    .filter(|t| t.visible)
+                    .flat_map(|t| t.entities()),
+            )
+            .chain(self.pointer.filter(|_| state.pointer_visible).map(|p| p.0))
+            .collect()
+    }
+}
+
+impl Tick {
+    fn entities(&self) -> impl Iterator<Item = Entity> {
+        std::iter::once(self.line.0).chain(self.label.map(|l| l.0))
+    }
+}
+
+fn number_to_point(range: &Range<f32>, center: Point, length: f32, number: f32) -> Point {
+    let unit = length / (range.end - range.start);
+    point(
+        center.x - length / 2.0 + (number - range.start) * unit,
+        center.y,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::AnimationType;
+
+    #[test]
+    fn numbers_map_to_points() {
+        let p = number_to_point(&(-2.0..8.0), point(1.0, 0.5), 5.0, 3.0);
+        assert_eq!((p.x, p.y), (1.0, 0.5));
+        let p = number_to_point(&(-2.0..8.0), point(1.0, 0.5), 5.0, -2.0);
+        assert_eq!(p.x, -1.5);
+    }
+
+    fn fades(animations: &[EntityAnimations], entity: Entity) -> usize {
+        animations
+            .iter()
+            .filter(|a| a.entity == entity)
+            .flat_map(|a| a.animations.iter())
+            .filter(|a| matches!(a, AnimationType::Opacity(_)))
+            .count()
+    }
+
+    #[test]
+    fn parts_are_children_of_group() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let number_line = number_line(&mut scene, 0.0..2.0)
+            .unwrap()
+            .with_pointer(1.0)
+            .make();
+        number_line.set_range(&mut scene, 0.0..4.0).unwrap();
+        let ticks = number_line.ticks(&scene);
+        assert_eq!(ticks.len(), 5);
+        let world = scene.world.get_mut();
+        let children = &world.get::<Children>(number_line.group().0).unwrap().0;
+        let pointer = number_line.pointer().unwrap().0;
+        assert!(children.contains(&number_line.line().0));
+        assert!(children.contains(&pointer));
+        for tick in ticks {
+            assert!(tick.entities().all(|e| children.contains(&e)));
+        }
+    }
+
+    #[test]
+    fn pointer_fades_outside_range() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let number_line = number_line(&mut scene, 0.0..4.0)
+            .unwrap()
+            .with_pointer(3.0)
+            .make();
+        let pointer = number_line.pointer().unwrap().0;
+
+        let animations = number_line.set_range(&mut scene, 0.0..2.0).unwrap();
+        assert_eq!(fades(&animations, pointer), 1);
+        let shown = number_line.show_creation(&scene);
+        assert!(shown.iter().all(|a| a.entity != pointer));
+
+        let animations = number_line.set_value(&mut scene, 1.0);
+        assert_eq!(fades(&animations, pointer), 1);
+        let animations = number_line.set_value(&mut scene, 2.0);
+        assert_eq!(fades(&animations, pointer), 0);
+        let shown = number_line.show_creation(&scene);
+        assert!(shown.iter().any(|a| a.entity == pointer));
+    }
+
+    #[test]
+    fn copies_share_range_and_value() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let number_line = number_line(&mut scene, 0.0..4.0)
+            .unwrap()
+            .with_pointer(1.0)
+            .make();
+        let copy = number_line.clone();
+        number_line.set_range(&mut scene, 0.0..8.0).unwrap();
+        number_line.set_value(&mut scene, 6.0);
+        assert_eq!(copy.range(&scene), 0.0..8.0);
+        assert_eq!(copy.value(&scene), 6.0);
+        let p = copy.number_to_point(&scene, 8.0);
+        assert_eq!(p.x, 2.0);
+    }
+
+    #[test]
+    fn tick_count_is_capped() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let number_line = number_line(&mut scene, 0.0..10.0).unwrap().make();
+        assert_eq!(number_line.ticks(&scene).len(), 11);
+        number_line.set_range(&mut scene, 0.0..1.0e6).unwrap();
+        let ticks = number_line.ticks(&scene).len() as i64;
+        assert!(ticks > 1 && ticks <= MAX_TICKS);
+        let state = number_line.state(&scene);
+        assert!(state.ticks.len() as i64 <= 11 + MAX_TICKS);
+        assert_eq!(visible_ticks(&(0.0..1.0e6), 1.0)[1], 50_000);
+    }
+
+    #[test]
+    fn invalid_ranges_are_rejected() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        assert!(number_line(&mut scene, 1.0..-1.0).is_err());
+        assert!(number_line(&mut scene, 1.0..1.0).is_err());
+        assert!(number_line(&mut scene, 0.0..f32::INFINITY).is_err());
+        let number_line = number_line(&mut scene, 0.0..1.0).unwrap().make();
+        assert!(number_line.set_range(&mut scene, 2.0..2.0).is_err());
+        assert_eq!(number_line.range(&scene), 0.0..1.0);
+    }
+}
@@ -0,0 +1,393 @@
+use super::axes::{tick_values, Coordinates};
+use super::common::*;
+use crate::{Complex, EmptyId, LineId, PathId, PointMap};
+use std::ops::Range;
+
+/// Builder for a coordinate grid covering a region of the plane.
+///
+/// Grid lines are made of many short segments, so that they bend smoothly
+/// when the plane is deformed with [NumberPlaneId::apply_function].
+pub struct NumberPlaneBuilder<'a> {
+    x_range: Range<f32>,
+    y_range: Range<f32>,
+    unit: f32,
+    x_step: f32,
+    y_step: f32,
+    faded_lines: usize,
+    resolution: f32,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    faded_color: Color,
+    axes_color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> NumberPlaneBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        let stroke_color = Color::new(0.16, 0.55, 0.75);
+        Self {
+            x_range: -7.0..7.0,
+            y_range: -4.0..4.0,
+            unit: 1.0,
+            x_step: 1.0,
+            y_step: 1.0,
+            faded_lines: 1,
+            resolution: 10.0,
+            stroke_weight: StrokeWeight(2.0),
+            stroke_color,
+            faded_color: Color::new(0.08, 0.27, 0.37),
+            axes_color: Color::WHITE,
+            position: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_x_range(mut self, range: Range<f32>) -> Self {
+        self.x_range = range;
+        self
+    }
+    pub fn with_y_range(mut self, range: Range<f32>) -> Self {
+        self.y_range = range;
+        self
+    }
+    /// Length in scene units of one unit of the plane.
+    pub fn with_unit_length(mut self, length: f32) -> Self {
+        self.unit = length;
+        self
+    }
+    /// Distance between major grid lines in units of the plane.
+    pub fn with_steps(mut self, x_step: f32, y_step: f32) -> Self {
+        self.x_step = x_step;
+        self.y_step = y_step;
+        self
+    }
+    /// Number of faded lines drawn between two major grid lines.
+    pub fn with_faded_lines(mut self, count: usize) -> Self {
+        self.faded_lines = count;
+        self
+    }
+    /// Number of segments per scene unit making up each grid line.
+    pub fn with_resolution(mut self, segments: f32) -> Self {
+        self.resolution = segments;
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.stroke_color = color;
+        self
+    }
+    pub fn with_faded_color(mut self, color: Color) -> Self {
+        self.faded_color = color;
+        self
+    }
+    pub fn with_axes_color(mut self, color: Color) -> Self {
+        self.axes_color = color;
+        self
+    }
+
+    /// Grid line from `from` to `to` in coordinates of the plane.
+    fn grid_line(
+        &mut self,
+        coords: &Coordinates,
+        from: (f32, f32),
+        to: (f32, f32),
+        color: Color,
+        weight: f32,
+    ) -> LineId {
+        let from = coords.coords_to_point(from.0, from.1);
+        let to = coords.coords_to_point(to.0, to.1);
+        let segments = ((to - from).length() * self.resolution).ceil().max(1.0) as usize;
+        let mut line = crate::line(self.scene)
+            .from(from.x, from.y)
+            .with_color(color)
+            .with_stroke_weight(weight);
+        for i in 1..=segments {
+            line = line.add(from.lerp(to, i as f32 / segments as f32));
+        }
+        line.make()
+    }
+
+    pub fn make(&mut self) -> NumberPlaneId {
+        let coords = Coordinates {
+            x_range: self.x_range.clone(),
+            y_range: self.y_range.clone(),
+            center: point(self.position.x, self.position.y),
+            x_length: (self.x_range.end - self.x_range.start) * self.unit,
+            y_length: (self.y_range.end - self.y_range.start) * self.unit,
+        };
+        let (x0, y0) = coords.origin();
+        let (x_min, x_max) = (self.x_range.start, self.x_range.end);
+        let (y_min, y_max) = (self.y_range.start, self.y_range.end);
+        let weight = self.stroke_weight.0;
+
+        // Faded lines go first so that they are drawn below the others.
+        let mut faded = Vec::new();
+        let divisions = self.faded_lines as f32 + 1.0;
+        if self.faded_lines > 0 {
+            let step = self.x_step / divisions;
+            for x in tick_values(&self.x_range, step) {
+                if !is_multiple(x, self.x_step) {
+                    let id = self.grid_line(
+                        &coords,
+                        (x, y_min),
+                        (x, y_max),
+                        self.faded_color,
+                        weight / 2.0,
+                    );
+                    faded.push(id);
+                }
+            }
+            let step = self.y_step / divisions;
+            for y in tick_values(&self.y_range, step) {
+                if !is_multiple(y, self.y_step) {
+                    let id = self.grid_line(
+                        &coords,
+                        (x_min, y),
+                        (x_max, y),
+                        self.faded_color,
+                        weight / 2.0,
+                    );
+                    faded.push(id);
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        for x in tick_values(&self.x_range, self.x_step) {
+            if (x - x0).abs() > EPS {
+                lines.push(self.grid_line(
+                    &coords,
+                    (x, y_min),
+                    (x, y_max),
+                    self.stroke_color,
+                    weight,
+                ));
+            }
+        }
+        for y in tick_values(&self.y_range, self.y_step) {
+            if (y - y0).abs() > EPS {
+                lines.push(self.grid_line(
+                    &coords,
+                    (x_min, y),
+                    (x_max, y),
+                    self.stroke_color,
+                    weight,
+                ));
+            }
+        }
+
+        let x_axis = self.grid_line(&coords, (x_min, y0), (x_max, y0), self.axes_color, weight);
+        let y_axis = self.grid_line(&coords, (x0, y_min), (x0, y_max), self.axes_color, weight);
+
+        let members: Vec<Entity> = faded
+            .iter()
+            .chain(lines.iter())
+            .chain([x_axis, y_axis].iter())
+            .map(|id| id.0)
+            .collect();
+        let mut group = crate::empty(self.scene);
+        for entity in members.iter() {
+            group = group.add(*entity);
+        }
+        let group = group.with_position(self.position.x, self.position.y).make();
+        self.scene
+            .world
+            .get_mut()
+            .entity_mut(group.0)
+            .insert(NumberPlaneState { members });
+
+        NumberPlaneId {
+            group,
+            coords,
+            x_axis,
+            y_axis,
+            lines,
+            faded,
+        }
+    }
+}
+
+crate::stroke_builder!(NumberPlaneBuilder);
+crate::position_builder!(NumberPlaneBuilder);
+
+fn is_multiple(value: f32, step: f32) -> bool {
+    let ratio = value / step;
+    (ratio - ratio.round()).abs() < EPS
+}
+
+pub fn number_plane(scene: &mut Scene) -> NumberPlaneBuilder<'_> {
+    NumberPlaneBuilder::new(scene)
+}
+
+/// Objects deformed along with a number plane, stored on its group so that
+/// every copy of a [NumberPlaneId] sees the same objects.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct NumberPlaneState {
+    /// Grid lines and objects given to [NumberPlaneId::add].
+    members: Vec<Entity>,
+}
+
+/// Coordinate grid made of several line objects.
+///
+/// The whole plane, along with the objects given to [NumberPlaneId::add],
+/// can be deformed with [NumberPlaneId::apply_matrix] and
+/// [NumberPlaneId::apply_function].
+#[derive(Debug, Clone)]
+pub struct NumberPlaneId {
+    group: EmptyId,
+    coords: Coordinates,
+    x_axis: LineId,
+    y_axis: LineId,
+    lines: Vec<LineId>,
+    faded: Vec<LineId>,
+}
+
+impl NumberPlaneId {
+    /// [Empty](crate::Empty) object holding the grid lines as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn x_axis(&self) -> LineId {
+        self.x_axis
+    }
+    pub fn y_axis(&self) -> LineId {
+        self.y_axis
+    }
+    /// Major grid lines, except for the axes.
+    pub fn lines(&self) -> &[LineId] {
+        &self.lines
+    }
+    pub fn faded_lines(&self) -> &[LineId] {
+        &self.faded
+    }
+    /// Mapping between coordinates of the plane before any deformation and
+    /// points of the scene.
+    pub fn coordinates(&self) -> &Coordinates {
+        &self.coords
+    }
+    pub fn coords_to_point(&self, x: f32, y: f32) -> Point {
+        self.coords.coords_to_point(x, y)
+    }
+    pub fn point_to_coords(&self, p: Point) -> (f32, f32) {
+        self.coords.point_to_coords(p)
+    }
+    /// Make an object, e.g. a vector drawn on the plane, deform along with it.
+    pub fn add(&self, scene: &mut Scene, object: impl Into<Entity>) {
+        let entity = object.into();
+        scene
+            .world
+            .get_mut()
+            .get_mut::<NumberPlaneState>(self.group.0)
+            .expect("number plane should be in the scene")
+            .members
+            .push(entity);
+    }
+
+    /// Deform the plane by the linear map `[[a, b], [c, d]]` of its
+    /// coordinates, which sends `(x, y)` to `(a x + b y, c x + d y)`.
+    pub fn apply_matrix(&self, scene: &Scene, matrix: [[f32; 2]; 2]) -> Vec<EntityAnimations> {
+        let to_scene = self.coords.to_scene();
+        match to_scene.inverse() {
+            Some(to_coords) => self.apply_map(
+                scene,
+                PointMap::affine(
+                    to_coords
+                        .transform(Transform::from_matrix(matrix))
+                        .transform(to_scene),
+                ),
+            ),
+            None => Vec::new(),
+        }
+    }
+
+    /// Deform the plane by moving every point with coordinates `p` to the
+    /// point with coordinates `f(p)`.
+    pub fn apply_function(
+        &self,
+        scene: &Scene,
+        f: impl Fn(Point) -> Point + Send + Sync + 'static,
+    ) -> Vec<EntityAnimations> {
+        let coords = self.coords.clone();
+        self.apply_map(
+            scene,
+            PointMap::new(move |p| {
+                let (x, y) = coords.point_to_coords(p);
+                let q = f(point(x, y));
+                coords.coords_to_point(q.x, q.y)
+            }),
+        )
+    }
+
+    /// Deform the plane by the complex function `f` of its coordinates.
+    pub fn apply_complex_function(
+        &self,
+        scene: &Scene,
+        f: impl Fn(Complex) -> Complex + Send + Sync + 'static,
+    ) -> Vec<EntityAnimations> {
+        self.apply_function(scene, move |p| f(p.into()).into())
+    }
+
+    fn apply_map(&self, scene: &Scene, map: PointMap) -> Vec<EntityAnimations> {
+        let world = scene.world.borrow();
+        let state = world
+            .get::<NumberPlaneState>(self.group.0)
+            .expect("number plane should be in the scene");
+        state
+            .members
+            .iter()
+            .map(|entity| EntityAnimations {
+                entity: *entity,
+                animations: Animation::map(map.clone()).into(),
+            })
+            .collect()
+    }
+
+    /// Show the grid lines, but not the objects given to
+    /// [add](NumberPlaneId::add).
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::show_creation).collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::fade_in).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.parts().map(EntityAnimations::fade_out).collect()
+    }
+
+    /// Faded lines, major lines and axes.
+    fn parts(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.faded
+            .iter()
+            .chain(self.lines.iter())
+            .copied()
+            .chain([self.x_axis, self.y_axis])
+            .map(|id| id.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faded_lines_skip_major_lines() {
+        let faded: Vec<f32> = tick_values(&(-1.0..1.0), 0.5)
+            .into_iter()
+            .filter(|x| !is_multiple(*x, 1.0))
+            .collect();
+        assert_eq!(faded, vec![-0.5, 0.5]);
+    }
+
+    #[test]
+    fn copies_of_plane_share_members() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let plane = number_plane(&mut scene).make();
+        let copy = plane.clone();
+        let vector = crate::arrow(&mut scene).make();
+        copy.add(&mut scene, vector);
+        let animations = plane.apply_matrix(&scene, [[1.0, 1.0], [0.0, 1.0]]);
+        assert!(animations.iter().any(|a| a.entity == vector.0));
+        assert!(animations.iter().any(|a| a.entity == plane.x_axis().0));
+        let animations = plane.apply_function(&scene, |p| p);
+        assert_eq!(animations.len(), plane.parts().count() + 1);
+    }
+}
@@ -0,0 +1,277 @@
+use super::common::*;
+use nannou::lyon::geom::{Angle as LyonAngle, ArcFlags, Translation};
+use nannou::lyon::math::Vector;
+use nannou::lyon::path::builder::{SvgPathBuilder, WithSvg};
+use nannou::lyon::path::path::Builder;
+
+/// Component indicating an arbitrary path built with [PathBuilder].
+#[derive(Component)]
+pub struct CustomPath;
+
+/// Builder for arbitrary shapes made of lines, Bezier curves and elliptic arcs,
+/// following the commands of SVG paths.
+///
+/// Like [Line](crate::Line), the resulting path is centered around the centroid of the
+/// points passed to the builder, which also becomes the position of the object.
+pub struct PathBuilder<'a> {
+    builder: WithSvg<Builder>,
+    points: Vec<Point>,
+    closed: bool,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> PathBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        let fill_color = Color::random();
+        Self {
+            builder: Path::svg_builder(),
+            points: Vec::new(),
+            closed: false,
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    /// Start a new sub-path at `to`.
+    pub fn move_to(mut self, to: Point) -> Self {
+        self.builder.move_to(to);
+        self.push(to);
+        self
+    }
+    pub fn line_to(mut self, to: Point) -> Self {
+        self.builder.line_to(to);
+        self.push(to);
+        self
+    }
+    pub fn quadratic_to(mut self, ctrl: Point, to: Point) -> Self {
+        self.builder.quadratic_bezier_to(ctrl, to);
+        self.push(to);
+        self
+    }
+    pub fn cubic_to(mut self, ctrl1: Point, ctrl2: Point, to: Point) -> Self {
+        self.builder.cubic_bezier_to(ctrl1, ctrl2, to);
+        self.push(to);
+        self
+    }
+    /// Elliptic arc to `to`, with the same parameters as the SVG `A` command.
+    pub fn arc_to(
+        mut self,
+        radii: Vector,
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: Point,
+    ) -> Self {
+        let flags = ArcFlags { large_arc, sweep };
+        self.builder
+            .arc_to(radii, LyonAngle::radians(x_rotation), flags, to);
+        self.push(to);
+        self
+    }
+    /// Close the current sub-path with a line back to its start. The path
+    /// is closed, and thus filled, when its last sub-path is closed.
+    pub fn close(mut self) -> Self {
+        self.builder.close();
+        self.closed = true;
+        self
+    }
+    /// Record the end point of a command, which continues or starts an open
+    /// sub-path.
+    fn push(&mut self, to: Point) {
+        self.points.push(to);
+        self.closed = false;
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::angle_builder!(PathBuilder);
+crate::stroke_builder!(PathBuilder);
+crate::fill_builder!(PathBuilder);
+
+impl Create<PathId> for PathBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> PathId {
+        let position = if self.points.is_empty() {
+            Position::default()
+        } else {
+            Position::from_points(&self.points)
+        };
+        let builder = std::mem::replace(&mut self.builder, Path::svg_builder());
+        // Translate the path so that it is centered around point (0,0)
+        let raw = builder
+            .build()
+            .transformed(&Translation::new(-position.x, -position.y));
+
+        insert_path(
+            self.scene,
+            Path::new(raw, self.closed),
+            position,
+            self.angle,
+            self.stroke_weight,
+            self.stroke_color,
+            self.closed.then_some(self.fill_color),
+        )
+    }
+}
+
+/// Spawn an object for a path that is already centered around its position.
+/// The object is filled only when a `fill_color` is given.
+pub(crate) fn insert_path(
+    scene: &mut Scene,
+    path: Path,
+    position: Position,
+    angle: Angle,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Option<Color>,
+) -> PathId {
+    let depth = scene.increment_counter();
+    let world = scene.world.get_mut();
+    let scale = Scale::ONE;
+    let size = path.size();
+
+    let transform = Transform::identity()
+        .scale(scale)
+        .rotate(angle)
+        .translate(position.into());
+    let screen_transform = scene.transform;
+
+    let global_path = PixelPath(
+        path.clone()
+            .transform(&transform.transform(screen_transform)),
+    );
+
+    let id = world
+        .spawn_empty()
+        .insert(CustomPath)
+        .insert(size)
+        .insert(BoundingSize(size))
+        .insert(scale)
+        .insert(position)
+        .insert(angle)
+        .insert(stroke_weight)
+        .insert(StrokeColor(stroke_color))
+        .insert(FillColor(fill_color.unwrap_or(stroke_color)))
+        .insert(Opacity(0.0))
+        .insert(depth)
+        .insert(PathCompletion(0.0))
+        .insert(path)
+        .insert(global_path)
+        .insert(transform)
+        .insert(HasFill(fill_color.is_some()))
+        .id();
+
+    id.into()
+}
+
+pub fn path(scene: &mut Scene) -> PathBuilder<'_> {
+    PathBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct PathId(pub(crate) Entity);
+crate::into_entity!(PathId);
+
+impl WithStroke for PathId {}
+impl WithFill for PathId {}
+impl WithColor for PathId {}
+impl WithPath for PathId {}
+impl WithPosition for PathId {}
+impl WithAngle for PathId {}
+impl WithStrokeWeight for PathId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nannou::geom::Rect;
+
+    fn made(builder: PathBuilder) -> (Position, Path) {
+        let mut builder = builder;
+        let id = builder.make();
+        let world = builder.scene.world.get_mut();
+        (
+            *world.get::<Position>(id.0).unwrap(),
+            world.get::<Path>(id.0).unwrap().clone(),
+        )
+    }
+
+    #[test]
+    fn path_is_centered_on_centroid_of_points() {
+        let mut scene = Scene::new(Rect::from_w_h(640.0, 480.0));
+        let (position, path) = made(
+            path(&mut scene)
+                .move_to(point(0.0, 0.0))
+                .line_to(point(3.0, 0.0))
+                .line_to(point(3.0, 3.0))
+                .close(),
+        );
+        assert!((position.x - 2.0).abs() < 1.0e-6 && (position.y - 1.0).abs() < 1.0e-6);
+        let first = path.raw.iter().next().unwrap().from();
+        assert!((first.x + 2.0).abs() < 1.0e-6 && (first.y + 1.0).abs() < 1.0e-6);
+        assert!(path.closed);
+    }
+
+    #[test]
+    fn path_is_closed_only_if_last_subpath_is() {
+        let mut scene = Scene::new(Rect::from_w_h(640.0, 480.0));
+        let (_, shape) = made(
+            path(&mut scene)
+                .move_to(point(0.0, 0.0))
+                .line_to(point(1.0, 0.0))
+                .line_to(point(1.0, 1.0))
+                .close()
+                .move_to(point(2.0, 0.0))
+                .line_to(point(3.0, 0.0)),
+        );
+        assert!(!shape.closed);
+
+        let (_, shape) = made(
+            path(&mut scene)
+                .move_to(point(0.0, 0.0))
+                .line_to(point(1.0, 0.0))
+                .move_to(point(2.0, 0.0))
+                .line_to(point(3.0, 0.0))
+                .line_to(point(3.0, 1.0))
+                .close(),
+        );
+        assert!(shape.closed);
+    }
+
+    #[test]
+    fn path_maps_apply_from_first_frame() {
+        let mut scene = Scene::new(Rect::from_w_h(640.0, 480.0));
+        let rect = Rect::from_w_h(640.0, 480.0);
+        let id = path(&mut scene)
+            .move_to(point(0.0, 0.0))
+            .line_to(point(1.0, 0.0))
+            .make();
+        scene.update(0.0, rect);
+        let right = |scene: &mut Scene| {
+            let world = scene.world.get_mut();
+            let path = world.get::<Path>(id.0).unwrap();
+            path.raw.iter().map(|e| e.to().x).fold(f32::MIN, f32::max)
+        };
+        let start = right(&mut scene);
+
+        scene
+            .play(id.apply_matrix([[2.0, 0.0], [0.0, 1.0]]))
+            .run_time(1.0)
+            .rate_func(EaseType::Linear);
+        scene.update(1.0, rect);
+        assert!((right(&mut scene) - (start + 0.5)).abs() < 1.0e-4);
+        scene.update(1.5, rect);
+        assert!((right(&mut scene) - (start + 1.0)).abs() < 1.0e-4);
+    }
+}
@@ -0,0 +1,235 @@
+use super::common::*;
+use core::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Component indicating a closed polygon.
+///
+/// The vertices are stored relative to the center of the polygon. When the
+/// [Size] of a polygon changes, the vertices are stretched to fit the new size.
+#[derive(Component, Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// Create a polygon from arbitrary vertices. Like [Rectangle](crate::Rectangle),
+    /// the polygon is centered on the center of its bounding box.
+    pub fn from_points(points: &[Point]) -> Self {
+        let (min, max) = bounds(points);
+        let center = point((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+        Self {
+            vertices: points
+                .iter()
+                .map(|p| point(p.x - center.x, p.y - center.y))
+                .collect(),
+        }
+    }
+
+    /// Create a regular polygon with `n` vertices on a circle of given radius,
+    /// with the first vertex pointing up. Like [Polygon::from_points], it is
+    /// centered on the center of its bounding box, which differs from the
+    /// center of the circle when `n` is odd.
+    pub fn regular(n: usize, radius: f32) -> Self {
+        let n = n.max(3);
+        let points: Vec<Point> = (0..n)
+            .map(|i| polar(radius, FRAC_PI_2 + TAU * i as f32 / n as f32))
+            .collect();
+        Self::from_points(&points)
+    }
+
+    /// Create a star with `n` points, alternating between vertices on the
+    /// outer and inner radius, with the first point pointing up. Like
+    /// [Polygon::regular], it is centered on the center of its bounding box.
+    pub fn star(n: usize, outer: f32, inner: f32) -> Self {
+        let n = n.max(2);
+        let points: Vec<Point> = (0..2 * n)
+            .map(|i| {
+                let radius = if i % 2 == 0 { outer } else { inner };
+                polar(radius, FRAC_PI_2 + PI * i as f32 / n as f32)
+            })
+            .collect();
+        Self::from_points(&points)
+    }
+
+    fn size(&self) -> Size {
+        Size::from_points(&self.vertices)
+    }
+}
+
+fn polar(radius: f32, angle: f32) -> Point {
+    point(radius * angle.cos(), radius * angle.sin())
+}
+
+fn bounds(points: &[Point]) -> (Point, Point) {
+    if points.is_empty() {
+        return (point(0.0, 0.0), point(0.0, 0.0));
+    }
+    points.iter().fold(
+        (point(f32::MAX, f32::MAX), point(f32::MIN, f32::MIN)),
+        |(min, max), p| (min.min(*p), max.max(*p)),
+    )
+}
+
+impl PathComponent for Polygon {
+    fn path(&self, size: &Size) -> Path {
+        let (sx, sy) = self.size().scale_factor(size);
+        let scale = |p: &Point| point(p.x * sx, p.y * sy);
+
+        let mut builder = Path::svg_builder();
+        if let Some((first, rest)) = self.vertices.split_first() {
+            builder.move_to(scale(first));
+            for p in rest {
+                builder.line_to(scale(p));
+            }
+            builder.close();
+        }
+
+        Path::new(builder.build(), true)
+    }
+}
+
+pub struct PolygonBuilder<'a> {
+    polygon: Polygon,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    position: Position,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> PolygonBuilder<'a> {
+    fn new(scene: &'a mut Scene, polygon: Polygon) -> Self {
+        let fill_color = Color::random();
+        Self {
+            polygon,
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            position: Default::default(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::angle_builder!(PolygonBuilder);
+crate::stroke_builder!(PolygonBuilder);
+crate::position_builder!(PolygonBuilder);
+crate::fill_builder!(PolygonBuilder);
+
+impl Create<PolygonId> for PolygonBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> PolygonId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let size = self.polygon.size();
+        let path = self.polygon.path(&size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(self.angle)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(self.polygon.clone())
+            .insert(size)
+            .insert(BoundingSize(size))
+            .insert(scale)
+            .insert(self.position)
+            .insert(self.angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.fill_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(true))
+            .id();
+
+        id.into()
+    }
+}
+
+/// Polygon through the given points, positioned at the center of their
+/// bounding box.
+pub fn polygon<'a>(scene: &'a mut Scene, points: &[Point]) -> PolygonBuilder<'a> {
+    let (min, max) = bounds(points);
+    let mut builder = PolygonBuilder::new(scene, Polygon::from_points(points));
+    builder.position = Position {
+        x: (min.x + max.x) / 2.0,
+        y: (min.y + max.y) / 2.0,
+    };
+    builder
+}
+
+pub fn regular_polygon(scene: &mut Scene, n: usize, radius: f32) -> PolygonBuilder<'_> {
+    PolygonBuilder::new(scene, Polygon::regular(n, radius))
+}
+
+pub fn star(scene: &mut Scene, n: usize, outer: f32, inner: f32) -> PolygonBuilder<'_> {
+    PolygonBuilder::new(scene, Polygon::star(n, outer, inner))
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct PolygonId(pub(crate) Entity);
+crate::into_entity!(PolygonId);
+
+impl WithStroke for PolygonId {}
+impl WithFill for PolygonId {}
+impl WithColor for PolygonId {}
+impl WithPath for PolygonId {}
+impl WithPosition for PolygonId {}
+impl WithAngle for PolygonId {}
+impl WithSize for PolygonId {}
+impl WithStrokeWeight for PolygonId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_is_centered() {
+        let polygon = Polygon::from_points(&[point(1.0, 1.0), point(3.0, 1.0), point(3.0, 2.0)]);
+        let (min, max) = bounds(&polygon.vertices);
+        assert_eq!((min.x, min.y, max.x, max.y), (-1.0, -0.5, 1.0, 0.5));
+    }
+
+    #[test]
+    fn star_alternates_radii() {
+        let star = Polygon::star(5, 1.0, 0.5);
+        assert_eq!(star.vertices.len(), 10);
+        // The first point is straight above the center of the circles.
+        let center = point(star.vertices[0].x, star.vertices[0].y - 1.0);
+        assert!(center.x.abs() < 1.0e-6);
+        assert!(((star.vertices[1] - center).length() - 0.5).abs() < 1.0e-6);
+        let (min, max) = bounds(&star.vertices);
+        assert!((min.y + max.y).abs() < 1.0e-6 && (min.x + max.x).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn odd_regular_polygon_is_centered_on_bounding_box() {
+        let pentagon = Polygon::regular(5, 1.0);
+        let (min, max) = bounds(&pentagon.vertices);
+        assert!((min.x + max.x).abs() < 1.0e-6);
+        assert!((min.y + max.y).abs() < 1.0e-6);
+        // The center of the circle lies below the center of the box.
+        assert!(pentagon.vertices[0].y < 1.0 - 1.0e-3);
+    }
+}
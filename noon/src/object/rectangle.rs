@@ -3,8 +3,8 @@ use super::common::*;
 #[derive(Component)]
 pub struct Rectangle;
 
-impl Rectangle {
-    fn path(size: &Size) -> Path {
+impl PathComponent for Rectangle {
+    fn path(&self, size: &Size) -> Path {
         let mut builder = Path::svg_builder();
         let start = point(size.width / 2.0, 0.0);
         builder.move_to(start);
@@ -63,7 +63,7 @@ impl Create<RectangleId> for RectangleBuilder<'_> {
         let depth = self.scene.increment_counter();
         let world = self.scene.world.get_mut();
         let scale = Scale::ONE;
-        let path = Rectangle::path(&self.size);
+        let path = Rectangle.path(&self.size);
         let transform = Transform::identity()
             .scale(scale)
             .rotate(self.angle)
@@ -79,6 +79,7 @@ impl Create<RectangleId> for RectangleBuilder<'_> {
             .spawn_empty()
             .insert(Rectangle)
             .insert(self.size)
+            .insert(BoundingSize(self.size))
             .insert(scale)
             .insert(self.position)
             .insert(self.angle)
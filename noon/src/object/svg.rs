@@ -0,0 +1,562 @@
+use super::common::*;
+use super::path::insert_path;
+use crate::path::svg::{parse, parse_numbers, SvgPathError};
+use crate::{EmptyId, PathId, ZOOM};
+use core::f32::consts::TAU;
+use nannou::lyon::algorithms::aabb::bounding_rect;
+use nannou::lyon::geom::{Angle as LyonAngle, Translation};
+use nannou::lyon::math::{Rect, Transform as Matrix, Vector};
+use nannou::lyon::path::Path as LyonPath;
+use std::path::Path as FilePath;
+use xml::reader::{EventReader, XmlEvent};
+
+/// Error while loading an SVG document.
+#[derive(Debug)]
+pub enum SvgError {
+    Io(std::io::Error),
+    Xml(xml::reader::Error),
+    Path(SvgPathError),
+}
+
+impl std::fmt::Display for SvgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgError::Io(e) => write!(f, "failed to read SVG file: {}", e),
+            SvgError::Xml(e) => write!(f, "malformed SVG document: {}", e),
+            SvgError::Path(e) => write!(f, "malformed SVG path data: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+impl From<std::io::Error> for SvgError {
+    fn from(e: std::io::Error) -> Self {
+        SvgError::Io(e)
+    }
+}
+
+impl From<xml::reader::Error> for SvgError {
+    fn from(e: xml::reader::Error) -> Self {
+        SvgError::Xml(e)
+    }
+}
+
+impl From<SvgPathError> for SvgError {
+    fn from(e: SvgPathError) -> Self {
+        SvgError::Path(e)
+    }
+}
+
+/// Presentation attributes inherited from enclosing groups.
+#[derive(Clone)]
+struct Style {
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    stroke_width: f32,
+    transform: Matrix,
+    /// Whether the element is rendered at all, which `display: none` or
+    /// a non-rendering ancestor such as `<defs>` rule out for good.
+    rendered: bool,
+    /// The CSS `visibility`, which descendants may override.
+    visible: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: Some(Color::BLACK),
+            stroke: None,
+            stroke_width: 1.0,
+            transform: Matrix::identity(),
+            rendered: true,
+            visible: true,
+        }
+    }
+}
+
+impl Style {
+    fn set(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        match name {
+            "fill" => {
+                if let Some(color) = parse_color(value) {
+                    self.fill = color;
+                }
+            }
+            "stroke" => {
+                if let Some(color) = parse_color(value) {
+                    self.stroke = color;
+                }
+            }
+            "stroke-width" => {
+                if let Some(&width) = parse_numbers(value).first() {
+                    self.stroke_width = width;
+                }
+            }
+            "display" => self.rendered &= value != "none",
+            "visibility" => self.visible = value != "hidden" && value != "collapse",
+            _ => (),
+        }
+    }
+}
+
+/// Shape of an SVG document, in document coordinates.
+struct Shape {
+    path: LyonPath,
+    closed: bool,
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    stroke_width: f32,
+    name: Option<String>,
+}
+
+/// Elements whose content is not rendered directly.
+const HIDDEN_ELEMENTS: &[&str] = &[
+    "defs",
+    "clipPath",
+    "mask",
+    "marker",
+    "pattern",
+    "symbol",
+    "linearGradient",
+    "radialGradient",
+    "style",
+    "title",
+    "desc",
+    "metadata",
+];
+
+/// Parse the drawable shapes of an SVG document in document order.
+fn parse_document(source: &str) -> Result<Vec<Shape>, SvgError> {
+    let mut shapes = Vec::new();
+    let mut styles = vec![Style::default()];
+
+    for event in EventReader::from_str(source) {
+        match event? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let attribute = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|a| a.name.local_name == key)
+                        .map(|a| a.value.as_str())
+                };
+                let mut style = styles.last().cloned().unwrap_or_default();
+                if HIDDEN_ELEMENTS.contains(&name.local_name.as_str()) {
+                    style.rendered = false;
+                }
+                for a in attributes.iter() {
+                    style.set(&a.name.local_name, &a.value);
+                }
+                if let Some(declarations) = attribute("style") {
+                    for declaration in declarations.split(';') {
+                        if let Some((key, value)) = declaration.split_once(':') {
+                            style.set(key.trim(), value);
+                        }
+                    }
+                }
+                if let Some(transform) = attribute("transform") {
+                    style.transform = parse_transform(transform).then(&style.transform);
+                }
+
+                if style.rendered && style.visible {
+                    let length = |key: &str| {
+                        attribute(key)
+                            .and_then(|v| parse_numbers(v).first().copied())
+                            .unwrap_or(0.0)
+                    };
+                    let geometry = match name.local_name.as_str() {
+                        "path" => Some(parse(attribute("d").unwrap_or(""))?),
+                        "rect" => Some(rectangle(
+                            length("x"),
+                            length("y"),
+                            length("width"),
+                            length("height"),
+                        )),
+                        "circle" => Some(ellipse(
+                            point(length("cx"), length("cy")),
+                            Vector::new(length("r"), length("r")),
+                        )),
+                        "ellipse" => Some(ellipse(
+                            point(length("cx"), length("cy")),
+                            Vector::new(length("rx"), length("ry")),
+                        )),
+                        "line" => Some(polyline(
+                            &[length("x1"), length("y1"), length("x2"), length("y2")],
+                            false,
+                        )),
+                        "polyline" => Some(polyline(
+                            &parse_numbers(attribute("points").unwrap_or("")),
+                            false,
+                        )),
+                        "polygon" => Some(polyline(
+                            &parse_numbers(attribute("points").unwrap_or("")),
+                            true,
+                        )),
+                        _ => None,
+                    };
+                    if let Some((path, closed)) = geometry {
+                        let t = &style.transform;
+                        let scale = (t.m11 * t.m22 - t.m12 * t.m21).abs().sqrt();
+                        shapes.push(Shape {
+                            path: path.transformed(t),
+                            closed,
+                            fill: style.fill,
+                            stroke: style.stroke,
+                            stroke_width: style.stroke_width * scale,
+                            name: attribute("id").map(String::from),
+                        });
+                    }
+                }
+                styles.push(style);
+            }
+            XmlEvent::EndElement { .. } => {
+                styles.pop();
+            }
+            _ => (),
+        }
+    }
+    Ok(shapes)
+}
+
+fn rectangle(x: f32, y: f32, width: f32, height: f32) -> (LyonPath, bool) {
+    let mut builder = LyonPath::svg_builder();
+    builder.move_to(point(x, y));
+    builder.line_to(point(x + width, y));
+    builder.line_to(point(x + width, y + height));
+    builder.line_to(point(x, y + height));
+    builder.close();
+    (builder.build(), true)
+}
+
+fn ellipse(center: Point, radii: Vector) -> (LyonPath, bool) {
+    let mut builder = LyonPath::svg_builder();
+    builder.move_to(point(center.x + radii.x, center.y));
+    builder.arc(
+        center,
+        radii,
+        LyonAngle::radians(TAU),
+        LyonAngle::radians(0.0),
+    );
+    builder.close();
+    (builder.build(), true)
+}
+
+fn polyline(coordinates: &[f32], closed: bool) -> (LyonPath, bool) {
+    let mut builder = LyonPath::svg_builder();
+    let mut points = coordinates.chunks_exact(2).map(|c| point(c[0], c[1]));
+    if let Some(first) = points.next() {
+        builder.move_to(first);
+        for p in points {
+            builder.line_to(p);
+        }
+        if closed {
+            builder.close();
+        }
+    }
+    (builder.build(), closed)
+}
+
+/// Parse the value of a `transform` attribute, e.g. `translate(10 20) rotate(45)`.
+fn parse_transform(text: &str) -> Matrix {
+    let mut matrix = Matrix::identity();
+    for item in text.split(')') {
+        let Some((name, arguments)) = item.split_once('(') else {
+            continue;
+        };
+        let v = parse_numbers(arguments);
+        let arg = |i: usize, default: f32| v.get(i).copied().unwrap_or(default);
+        let transform = match name.trim().trim_start_matches(',').trim() {
+            "matrix" if v.len() == 6 => Matrix::new(v[0], v[1], v[2], v[3], v[4], v[5]),
+            "translate" => Matrix::translation(arg(0, 0.0), arg(1, 0.0)),
+            "scale" => Matrix::scale(arg(0, 1.0), arg(1, arg(0, 1.0))),
+            "rotate" => {
+                let (cx, cy) = (arg(1, 0.0), arg(2, 0.0));
+                Matrix::translation(-cx, -cy)
+                    .then_rotate(LyonAngle::degrees(arg(0, 0.0)))
+                    .then_translate(Vector::new(cx, cy))
+            }
+            "skewX" => Matrix::new(1.0, 0.0, arg(0, 0.0).to_radians().tan(), 1.0, 0.0, 0.0),
+            "skewY" => Matrix::new(1.0, arg(0, 0.0).to_radians().tan(), 0.0, 1.0, 0.0, 0.0),
+            _ => continue,
+        };
+        // Transforms listed later are applied first.
+        matrix = transform.then(&matrix);
+    }
+    matrix
+}
+
+/// Parse a color value. Returns `Some(None)` for `none`, and `None` for
+/// values that are not understood such as gradients.
+fn parse_color(text: &str) -> Option<Option<Color>> {
+    let text = text.trim();
+    if text == "none" {
+        return Some(None);
+    }
+    let rgb = |r: f32, g: f32, b: f32| Some(Some(Color::new(r / 255.0, g / 255.0, b / 255.0)));
+    if let Some(hex) = text.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return match hex.len() {
+            3 => rgb(
+                (digit(0)? * 17) as f32,
+                (digit(1)? * 17) as f32,
+                (digit(2)? * 17) as f32,
+            ),
+            6 => rgb(byte(0)? as f32, byte(2)? as f32, byte(4)? as f32),
+            _ => None,
+        };
+    }
+    if let Some(arguments) = text.strip_prefix("rgb(") {
+        let channel = |s: &str| {
+            let s = s.trim().trim_end_matches(')');
+            match s.strip_suffix('%') {
+                Some(percent) => percent.trim().parse::<f32>().ok().map(|p| p * 2.55),
+                None => s.parse::<f32>().ok(),
+            }
+        };
+        let channels: Vec<f32> = arguments.split(',').filter_map(channel).collect();
+        return match channels[..] {
+            [r, g, b] => rgb(r, g, b),
+            _ => None,
+        };
+    }
+    let (r, g, b) = match text.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "lime" => (0, 255, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" | "aqua" => (0, 255, 255),
+        "magenta" | "fuchsia" => (255, 0, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "maroon" => (128, 0, 0),
+        "olive" => (128, 128, 0),
+        "navy" => (0, 0, 128),
+        "teal" => (0, 128, 128),
+        "purple" => (128, 0, 128),
+        "orange" => (255, 165, 0),
+        "brown" => (165, 42, 42),
+        "pink" => (255, 192, 203),
+        _ => return None,
+    };
+    rgb(r as f32, g as f32, b as f32)
+}
+
+pub struct SvgBuilder<'a> {
+    shapes: Vec<Shape>,
+    width: Option<f32>,
+    height: Option<f32>,
+    position: Position,
+    scene: &'a mut Scene,
+}
+
+impl<'a> SvgBuilder<'a> {
+    fn new(scene: &'a mut Scene, source: &str) -> Result<Self, SvgError> {
+        Ok(Self {
+            shapes: parse_document(source)?,
+            width: None,
+            height: None,
+            position: Default::default(),
+            scene,
+        })
+    }
+    /// Scale the drawing to the given width in scene units. By default,
+    /// one unit of the SVG document is drawn as one pixel.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+    /// Scale the drawing to the given height in scene units.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+    /// Create one object per shape of the document, grouped under an
+    /// [Empty](crate::Empty) object positioned at the center of the drawing.
+    pub fn make(&mut self) -> SvgId {
+        // Shapes without any path events, e.g. `<path d=""/>`, have no
+        // bounds to position them with.
+        let shapes: Vec<_> = std::mem::take(&mut self.shapes)
+            .into_iter()
+            .filter(|shape| shape.path.iter().next().is_some())
+            .collect();
+        let bounds = shapes
+            .iter()
+            .map(|s| bounding_rect(s.path.iter()))
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_else(Rect::zero);
+        let scale = match (self.width, self.height) {
+            (Some(width), _) if bounds.width() > 0.0 => width / bounds.width(),
+            (_, Some(height)) if bounds.height() > 0.0 => height / bounds.height(),
+            _ => 1.0 / ZOOM,
+        };
+        let center = bounds.center();
+        // Center the drawing on its position, flipping the y axis which
+        // points down in SVG documents.
+        let to_scene = Matrix::translation(-center.x, -center.y)
+            .then_scale(scale, -scale)
+            .then_translate(Vector::new(self.position.x, self.position.y));
+
+        let mut ids = Vec::new();
+        let mut names = Vec::new();
+        for shape in shapes {
+            if shape.fill.is_none() && shape.stroke.is_none() {
+                continue;
+            }
+            let raw = shape.path.transformed(&to_scene);
+            let position = bounding_rect(raw.iter()).center();
+            let raw = raw.transformed(&Translation::new(-position.x, -position.y));
+            let (stroke_weight, stroke_color) = match shape.stroke {
+                Some(color) => (StrokeWeight(shape.stroke_width * scale * ZOOM), color),
+                None => (StrokeWeight::NONE, shape.fill.unwrap_or(Color::BLACK)),
+            };
+            ids.push(insert_path(
+                self.scene,
+                Path::new(raw, shape.closed),
+                Position {
+                    x: position.x,
+                    y: position.y,
+                },
+                Angle::default(),
+                stroke_weight,
+                stroke_color,
+                shape.fill,
+            ));
+            names.push(shape.name);
+        }
+
+        let mut group = crate::empty(self.scene)
+            .with_position(self.position.x, self.position.y)
+            .with_size(bounds.width() * scale, bounds.height() * scale);
+        for &id in ids.iter() {
+            group = group.add(id);
+        }
+        SvgId {
+            group: group.make(),
+            shapes: ids,
+            names,
+        }
+    }
+}
+
+crate::position_builder!(SvgBuilder);
+
+pub fn svg_file(scene: &mut Scene, path: impl AsRef<FilePath>) -> Result<SvgBuilder<'_>, SvgError> {
+    let source = std::fs::read_to_string(path)?;
+    SvgBuilder::new(scene, &source)
+}
+
+pub fn svg_str<'a>(scene: &'a mut Scene, source: &str) -> Result<SvgBuilder<'a>, SvgError> {
+    SvgBuilder::new(scene, source)
+}
+
+/// Objects created from an SVG document. Each drawable element of the
+/// document is its own path object, in document order.
+#[derive(Debug, Clone)]
+pub struct SvgId {
+    group: EmptyId,
+    shapes: Vec<PathId>,
+    names: Vec<Option<String>>,
+}
+
+impl SvgId {
+    /// [Empty](crate::Empty) object holding all the shapes as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn shapes(&self) -> &[PathId] {
+        &self.shapes
+    }
+    /// Shape created from the element with the given `id` attribute.
+    pub fn get(&self, name: &str) -> Option<PathId> {
+        self.names
+            .iter()
+            .position(|n| n.as_deref() == Some(name))
+            .map(|i| self.shapes[i])
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.shapes.iter().map(|s| s.show_creation()).collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.shapes.iter().map(|s| s.fade_in()).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.shapes.iter().map(|s| s.fade_out()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_inherit_style_and_transform() {
+        let shapes = parse_document(
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+                <g fill="#ff0000" transform="translate(10 0)">
+                    <rect id="box" width="10" height="10"/>
+                    <circle cx="0" cy="0" r="5" style="fill:none;stroke:blue"/>
+                </g>
+                <defs><rect width="1" height="1"/></defs>
+            </svg>"##,
+        )
+        .unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0].name.as_deref(), Some("box"));
+        assert_eq!(shapes[0].fill, Some(Color::new(1.0, 0.0, 0.0)));
+        assert_eq!(bounding_rect(shapes[0].path.iter()).min_x(), 10.0);
+        assert!(shapes[1].fill.is_none());
+        assert_eq!(shapes[1].stroke, Some(Color::new(0.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn empty_shapes_are_skipped() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let svg = svg_str(
+            &mut scene,
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+                <path id="nothing" d="" fill="#ff0000"/>
+                <polygon points="" fill="#ff0000"/>
+                <rect id="box" x="10" y="10" width="10" height="10" fill="#ff0000"/>
+            </svg>"##,
+        )
+        .unwrap()
+        .make();
+        assert_eq!(svg.shapes().len(), 1);
+        assert!(svg.get("nothing").is_none());
+        let world = scene.world.get_mut();
+        let position = world.get::<Position>(svg.get("box").unwrap().0).unwrap();
+        assert!(position.x.is_finite() && position.y.is_finite());
+        assert!(position.x.abs() < 1.0e-6 && position.y.abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn visibility_does_not_reveal_hidden_elements() {
+        let shapes = parse_document(
+            r##"<svg xmlns="http://www.w3.org/2000/svg">
+                <defs><rect width="1" height="1" visibility="visible"/></defs>
+                <g display="none"><rect width="1" height="1" style="visibility:visible"/></g>
+                <g visibility="hidden">
+                    <rect width="1" height="1"/>
+                    <rect id="shown" width="1" height="1" visibility="visible"/>
+                </g>
+            </svg>"##,
+        )
+        .unwrap();
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].name.as_deref(), Some("shown"));
+    }
+
+    #[test]
+    fn transforms_apply_right_to_left() {
+        let m = parse_transform("translate(10, 0) scale(2)");
+        let p = m.transform_point(point(1.0, 1.0));
+        assert_eq!((p.x, p.y), (12.0, 2.0));
+    }
+}
@@ -0,0 +1,520 @@
+use super::common::*;
+use crate::component::Children;
+use crate::{EmptyId, LineId, TextId};
+use std::marker::PhantomData;
+
+/// Content of a table cell, either text to be created or an existing object.
+enum CellSource {
+    Text(String),
+    Object(Entity),
+}
+
+/// Widths of the columns and heights of the rows fitting the given cell
+/// sizes, with `padding` added on each side of every cell.
+fn fit(sizes: &[Vec<Size>], padding: (f32, f32)) -> (Vec<f32>, Vec<f32>) {
+    let columns = sizes.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut widths = vec![0.0_f32; columns];
+    let mut heights = vec![0.0_f32; sizes.len()];
+    for (i, row) in sizes.iter().enumerate() {
+        for (j, size) in row.iter().enumerate() {
+            widths[j] = widths[j].max(size.width + 2.0 * padding.0);
+            heights[i] = heights[i].max(size.height + 2.0 * padding.1);
+        }
+    }
+    (widths, heights)
+}
+
+/// Offsets of the boundaries between consecutive cells of given lengths,
+/// starting from zero.
+fn boundaries(lengths: &[f32]) -> Vec<f32> {
+    std::iter::once(0.0)
+        .chain(lengths.iter().scan(0.0, |sum, length| {
+            *sum += length;
+            Some(*sum)
+        }))
+        .collect()
+}
+
+/// Builder for a grid of objects laid out in rows and columns.
+///
+/// Each column is as wide as its widest cell, and each row as tall as its
+/// tallest cell, plus padding. Cells are centered within their slot.
+///
+/// Cells of the resulting [TableId] are returned as `C`, i.e. [TextId] for
+/// tables of texts and the type of the given objects otherwise.
+pub struct TableBuilder<'a, C = TextId> {
+    cells: Vec<Vec<CellSource>>,
+    padding: (f32, f32),
+    borders: bool,
+    font_size: f32,
+    text_color: Color,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    position: Position,
+    scene: &'a mut Scene,
+    cell: PhantomData<C>,
+}
+
+impl<'a, C: From<Entity>> TableBuilder<'a, C> {
+    fn new(scene: &'a mut Scene, cells: Vec<Vec<CellSource>>) -> Self {
+        Self {
+            cells,
+            padding: (0.2, 0.1),
+            borders: false,
+            font_size: 0.3,
+            text_color: Color::WHITE,
+            stroke_weight: StrokeWeight::THIN,
+            stroke_color: Color::WHITE,
+            position: Default::default(),
+            scene,
+            cell: PhantomData,
+        }
+    }
+    /// Space left between the content of a cell and its boundaries,
+    /// horizontally and vertically.
+    pub fn with_padding(mut self, horizontal: f32, vertical: f32) -> Self {
+        self.padding = (horizontal, vertical);
+        self
+    }
+    /// Draw lines around every cell.
+    pub fn with_borders(mut self) -> Self {
+        self.borders = true;
+        self
+    }
+    /// Font size of the cells given as text.
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+    /// Color of both the text cells and the borders.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self.stroke_color = color;
+        self
+    }
+
+    /// Create the text cells and move every cell in place, returning the
+    /// cells along with the column and row boundaries in scene units.
+    fn layout(&mut self) -> (Vec<Vec<Entity>>, Vec<f32>, Vec<f32>) {
+        let sources = std::mem::take(&mut self.cells);
+        let cells: Vec<Vec<Entity>> = sources
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| match cell {
+                        CellSource::Text(text) => crate::text(self.scene)
+                            .with_text(&text)
+                            .with_font_size(self.font_size)
+                            .with_color(self.text_color)
+                            .make()
+                            .into(),
+                        CellSource::Object(entity) => entity,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let world = self.scene.world.get_mut();
+        let sizes: Vec<Vec<Size>> = cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|entity| {
+                        let size = world
+                            .get::<Size>(*entity)
+                            .copied()
+                            .unwrap_or(Size::from(0.0, 0.0));
+                        let scale = world.get::<Scale>(*entity).copied().unwrap_or(Scale::ONE);
+                        Size::from(size.width * scale.x, size.height * scale.y)
+                    })
+                    .collect()
+            })
+            .collect();
+        let (widths, heights) = fit(&sizes, self.padding);
+        let width: f32 = widths.iter().sum();
+        let height: f32 = heights.iter().sum();
+        let left = self.position.x - width / 2.0;
+        let top = self.position.y + height / 2.0;
+        let xs: Vec<f32> = boundaries(&widths).iter().map(|x| left + x).collect();
+        let ys: Vec<f32> = boundaries(&heights).iter().map(|y| top - y).collect();
+
+        for (i, row) in cells.iter().enumerate() {
+            for (j, entity) in row.iter().enumerate() {
+                if let Some(mut position) = world.get_mut::<Position>(*entity) {
+                    position.x = (xs[j] + xs[j + 1]) / 2.0;
+                    position.y = (ys[i] + ys[i + 1]) / 2.0;
+                }
+            }
+        }
+        (cells, xs, ys)
+    }
+
+    pub fn make(&mut self) -> TableId<C> {
+        let (cells, xs, ys) = self.layout();
+
+        let mut borders = Vec::new();
+        if self.borders && xs.len() > 1 && ys.len() > 1 {
+            let (left, right) = (xs[0], xs[xs.len() - 1]);
+            let (top, bottom) = (ys[0], ys[ys.len() - 1]);
+            for y in ys.iter() {
+                borders.push(self.border(point(left, *y), point(right, *y)));
+            }
+            for x in xs.iter() {
+                borders.push(self.border(point(*x, top), point(*x, bottom)));
+            }
+        }
+
+        let mut group = crate::empty(self.scene);
+        for entity in cells.iter().flatten() {
+            group = group.add(*entity);
+        }
+        for line in borders.iter() {
+            group = group.add(*line);
+        }
+        let group = group
+            .with_position(self.position.x, self.position.y)
+            .with_size(xs[xs.len() - 1] - xs[0], ys[0] - ys[ys.len() - 1])
+            .make();
+        TableId {
+            group,
+            cells,
+            borders,
+            cell: PhantomData,
+        }
+    }
+
+    fn border(&mut self, from: Point, to: Point) -> LineId {
+        crate::line(self.scene)
+            .from(from.x, from.y)
+            .to(to.x, to.y)
+            .with_color(self.stroke_color)
+            .with_stroke_weight(self.stroke_weight.0)
+            .make()
+    }
+}
+
+crate::stroke_builder!(TableBuilder, C);
+crate::position_builder!(TableBuilder, C);
+
+/// Table of texts, one for each of the given strings.
+pub fn table<'a, R, S>(scene: &'a mut Scene, rows: &[R]) -> TableBuilder<'a>
+where
+    R: AsRef<[S]>,
+    S: ToString,
+{
+    let cells = rows
+        .iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .map(|s| CellSource::Text(s.to_string()))
+                .collect()
+        })
+        .collect();
+    TableBuilder::new(scene, cells)
+}
+
+/// Table laying out existing objects.
+pub fn table_of<R, E>(scene: &mut Scene, rows: R) -> TableBuilder<'_, E>
+where
+    R: IntoIterator,
+    R::Item: IntoIterator<Item = E>,
+    E: Into<Entity> + From<Entity>,
+{
+    let cells = rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|e| CellSource::Object(e.into()))
+                .collect()
+        })
+        .collect();
+    TableBuilder::new(scene, cells)
+}
+
+/// Grid of objects with optional borders.
+///
+/// Cells are addressed by row and column, starting from the top left, and
+/// can be retrieved by whole rows or columns.
+#[derive(Debug, Clone)]
+pub struct TableId<C = TextId> {
+    group: EmptyId,
+    cells: Vec<Vec<Entity>>,
+    borders: Vec<LineId>,
+    cell: PhantomData<C>,
+}
+
+impl<C: From<Entity>> TableId<C> {
+    /// [Empty](crate::Empty) object holding the cells and borders as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn rows(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn columns(&self) -> usize {
+        self.cells.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+    pub fn cell(&self, row: usize, column: usize) -> Option<C> {
+        self.cells.get(row)?.get(column).map(|e| C::from(*e))
+    }
+    pub fn row(&self, row: usize) -> Vec<C> {
+        self.cells
+            .get(row)
+            .map(|cells| cells.iter().map(|e| C::from(*e)).collect())
+            .unwrap_or_default()
+    }
+    pub fn column(&self, column: usize) -> Vec<C> {
+        self.cells
+            .iter()
+            .filter_map(|row| row.get(column))
+            .map(|e| C::from(*e))
+            .collect()
+    }
+    /// Lines around the cells, horizontal ones first from top to bottom,
+    /// then vertical ones from left to right.
+    pub fn borders(&self) -> &[LineId] {
+        &self.borders
+    }
+
+    /// Morph every cell into the cell at the same place in `other`, e.g. to
+    /// show a step of a computation. Cells without a counterpart in `other`
+    /// fade out, and cells of `other` without a counterpart here fade in.
+    pub fn morph<D>(&self, other: &TableId<D>) -> Vec<EntityAnimations> {
+        let mut animations = Vec::new();
+        let empty = Vec::new();
+        for i in 0..self.cells.len().max(other.cells.len()) {
+            let row = self.cells.get(i).unwrap_or(&empty);
+            let targets = other.cells.get(i).unwrap_or(&empty);
+            for (j, cell) in row.iter().enumerate() {
+                match targets.get(j) {
+                    Some(target) => animations.push(EntityAnimations::morph(*cell, *target)),
+                    None => animations.push(EntityAnimations::fade_out(*cell)),
+                }
+            }
+            for target in targets.iter().skip(row.len()) {
+                animations.push(EntityAnimations::fade_in(*target));
+            }
+        }
+        animations
+    }
+
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.entities()
+            .map(EntityAnimations::show_creation)
+            .collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.entities().map(EntityAnimations::fade_in).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.entities().map(EntityAnimations::fade_out).collect()
+    }
+
+    fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.cells
+            .iter()
+            .flatten()
+            .copied()
+            .chain(self.borders.iter().map(|line| line.0))
+    }
+}
+
+/// Builder for a matrix, i.e. a table of entries between square brackets.
+pub struct MatrixBuilder<'a> {
+    table: TableBuilder<'a>,
+    bracket_width: f32,
+}
+
+impl<'a> MatrixBuilder<'a> {
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.table.font_size = size;
+        self
+    }
+    /// Space left around each entry, horizontally and vertically.
+    pub fn with_padding(mut self, horizontal: f32, vertical: f32) -> Self {
+        self.table.padding = (horizontal, vertical);
+        self
+    }
+    /// Length of the horizontal ends of the brackets.
+    pub fn with_bracket_width(mut self, width: f32) -> Self {
+        self.bracket_width = width;
+        self
+    }
+    /// Color of both the entries and the brackets.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.table = self.table.with_color(color);
+        self
+    }
+    pub fn with_stroke_weight(mut self, weight: f32) -> Self {
+        self.table.stroke_weight = StrokeWeight(weight);
+        self
+    }
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.table.position = Position { x, y };
+        self
+    }
+
+    pub fn make(&mut self) -> MatrixId {
+        let entries = self.table.make();
+        let group = entries.group.0;
+        let scene = &mut *self.table.scene;
+        let (width, height) = {
+            let world = scene.world.get_mut();
+            let size = world
+                .get::<Size>(group)
+                .copied()
+                .unwrap_or(Size::from(0.0, 0.0));
+            (size.width, size.height)
+        };
+        let center = self.table.position;
+        let w = self.bracket_width;
+        let (top, bottom) = (center.y + height / 2.0, center.y - height / 2.0);
+        let mut bracket = |x: f32, w: f32| {
+            crate::line(scene)
+                .from(x + w, top)
+                .to(x, top)
+                .to(x, bottom)
+                .to(x + w, bottom)
+                .with_color(self.table.stroke_color)
+                .with_stroke_weight(self.table.stroke_weight.0)
+                .make()
+        };
+        let left = bracket(center.x - width / 2.0, w);
+        let right = bracket(center.x + width / 2.0, -w);
+
+        if let Some(mut children) = scene.world.get_mut().get_mut::<Children>(group) {
+            children.add(left);
+            children.add(right);
+        }
+        MatrixId {
+            entries,
+            brackets: (left, right),
+        }
+    }
+}
+
+/// Matrix of texts, one for each of the given entries.
+pub fn matrix<'a, R, S>(scene: &'a mut Scene, rows: &[R]) -> MatrixBuilder<'a>
+where
+    R: AsRef<[S]>,
+    S: ToString,
+{
+    MatrixBuilder {
+        table: table(scene, rows).with_padding(0.2, 0.08),
+        bracket_width: 0.1,
+    }
+}
+
+/// Table of entries between two brackets.
+#[derive(Debug, Clone)]
+pub struct MatrixId {
+    entries: TableId<TextId>,
+    brackets: (LineId, LineId),
+}
+
+impl MatrixId {
+    /// [Empty](crate::Empty) object holding the entries and brackets as children.
+    pub fn group(&self) -> EmptyId {
+        self.entries.group
+    }
+    /// Entries of the matrix, laid out as a table without borders.
+    pub fn entries(&self) -> &TableId<TextId> {
+        &self.entries
+    }
+    pub fn entry(&self, row: usize, column: usize) -> Option<TextId> {
+        self.entries.cell(row, column)
+    }
+    pub fn row(&self, row: usize) -> Vec<TextId> {
+        self.entries.row(row)
+    }
+    pub fn column(&self, column: usize) -> Vec<TextId> {
+        self.entries.column(column)
+    }
+    /// Left and right brackets.
+    pub fn brackets(&self) -> (LineId, LineId) {
+        self.brackets
+    }
+    /// Morph every entry into the entry at the same place in `other`, and the
+    /// brackets into the brackets of `other`. Entries without a counterpart
+    /// fade out or in, as in [TableId::morph].
+    pub fn morph(&self, other: &MatrixId) -> Vec<EntityAnimations> {
+        let mut animations = self.entries.morph(&other.entries);
+        animations.push(self.brackets.0.morph(other.brackets.0));
+        animations.push(self.brackets.1.morph(other.brackets.1));
+        animations
+    }
+
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        let mut animations = self.entries.show_creation();
+        animations.push(self.brackets.0.show_creation());
+        animations.push(self.brackets.1.show_creation());
+        animations
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        let mut animations = self.entries.fade_in();
+        animations.push(self.brackets.0.fade_in());
+        animations.push(self.brackets.1.fade_in());
+        animations
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        let mut animations = self.entries.fade_out();
+        animations.push(self.brackets.0.fade_out());
+        animations.push(self.brackets.1.fade_out());
+        animations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::AnimationType;
+
+    #[test]
+    fn columns_fit_widest_cell() {
+        let sizes = vec![
+            vec![Size::from(1.0, 0.2), Size::from(0.5, 0.3)],
+            vec![Size::from(0.4, 0.6)],
+        ];
+        let (widths, heights) = fit(&sizes, (0.1, 0.0));
+        let close = |a: &[f32], b: &[f32]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < EPS);
+        assert!(close(&widths, &[1.2, 0.7]));
+        assert!(close(&heights, &[0.3, 0.6]));
+        assert!(close(&boundaries(&widths), &[0.0, 1.2, 1.9]));
+    }
+
+    #[test]
+    fn cells_keep_their_types() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let circle = crate::circle(&mut scene).make();
+        let other = crate::circle(&mut scene).make();
+        let objects = table_of(&mut scene, [[circle, circle], [other, other]]).make();
+        assert_eq!(objects.cell(0, 0).map(|c| c.0), Some(circle.0));
+        let column: Vec<Entity> = objects.column(1).iter().map(|c| c.0).collect();
+        assert_eq!(column, vec![circle.0, other.0]);
+
+        let texts = table(&mut scene, &[["a", "b"]]).make();
+        let text: Option<TextId> = texts.cell(0, 1);
+        assert!(text.is_some());
+    }
+
+    #[test]
+    fn morph_fades_unmatched_cells() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let small = table(&mut scene, &[vec!["a", "b"], vec!["c", "d"]]).make();
+        let large = table(&mut scene, &[vec!["a", "b", "e"]]).make();
+        let animations = small.morph(&large);
+        let fades = |entity: Entity| {
+            animations
+                .iter()
+                .filter(|a| a.entity == entity)
+                .flat_map(|a| a.animations.iter())
+                .any(|a| matches!(a, AnimationType::Opacity(_)))
+        };
+        assert_eq!(animations.len(), 5);
+        assert!(fades(small.cell(1, 0).unwrap().0));
+        assert!(fades(small.cell(1, 1).unwrap().0));
+        assert!(fades(large.cell(0, 2).unwrap().0));
+        assert!(!fades(small.cell(0, 0).unwrap().0));
+    }
+}
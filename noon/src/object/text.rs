@@ -1,47 +1,496 @@
 use super::common::*;
+use crate::{EmptyId, FontFamily, FontId, FontStyle, Fonts, Interpolate, PathId, WithTextColor};
+use nannou::lyon::path::{Path as RawPath, PathEvent};
+use nannou::text::{Font, GlyphId, ScaledGlyph};
+use std::ops::{Add, Range};
+use std::path::Path as FilePath;
 
+/// Component indicating a text object, holding the string it displays.
 #[derive(Component)]
-pub struct Text;
+pub struct Text(pub(crate) String);
+
+pub use nannou::text::Justify;
+
+/// Layout options used when typesetting a [Text] object.
+///
+/// All lengths are given in scene units, i.e. the same units as [Position].
+#[derive(Debug, Component, Clone, Copy)]
+pub struct TextLayout {
+    /// Maximum width of a line before the text wraps onto the next line.
+    /// If `None`, lines only break at explicit newlines.
+    pub max_width: Option<f32>,
+    /// Horizontal alignment of each line.
+    pub justify: Justify,
+    /// Additional space between consecutive lines.
+    pub line_spacing: f32,
+    /// Additional space between consecutive characters.
+    pub letter_spacing: f32,
+}
+
+impl Default for TextLayout {
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            justify: Justify::Left,
+            line_spacing: 0.0,
+            letter_spacing: 0.0,
+        }
+    }
+}
+
+/// Font styles of byte ranges of a [Text].
+///
+/// Styles of overlapping ranges combine, e.g. a character in both a bold
+/// and an italic range is set in bold italic.
+#[derive(Debug, Component, Clone, Default)]
+pub struct TextStyles(pub(crate) Vec<(Range<usize>, FontStyle)>);
+
+impl TextStyles {
+    /// Style of the character at byte `index`.
+    pub(crate) fn at(&self, index: usize) -> FontStyle {
+        self.0
+            .iter()
+            .filter(|(range, _)| range.contains(&index))
+            .fold(FontStyle::REGULAR, |style, (_, s)| FontStyle {
+                bold: style.bold || s.bold,
+                italic: style.italic || s.italic,
+            })
+    }
+}
+
+/// Colors of the characters of a [Text] indexed by byte, overriding its
+/// [FillColor] and [StrokeColor] where set.
+#[derive(Debug, Component, Clone, Default, PartialEq)]
+pub struct TextColors(pub(crate) Vec<Option<Color>>);
+
+impl TextColors {
+    /// Colors setting only the characters in the byte `range`.
+    pub(crate) fn range(range: Range<usize>, color: Color) -> Self {
+        let mut colors = vec![None; range.end];
+        colors[range.start.min(range.end)..].fill(Some(color));
+        Self(colors)
+    }
+    /// Color of the character at byte `index`, if set.
+    pub(crate) fn get(&self, index: usize) -> Option<Color> {
+        self.0.get(index).copied().flatten()
+    }
+    /// Colors of the characters in the byte `range`, indexed from its start.
+    pub(crate) fn slice(&self, range: &Range<usize>) -> Self {
+        Self(range.clone().map(|i| self.get(i)).collect())
+    }
+    /// Copy of these colors where characters set by `other` but not here
+    /// are set to `color`.
+    pub(crate) fn filled(&self, other: &Self, color: Color) -> Self {
+        let mut colors = self.0.clone();
+        colors.resize(self.0.len().max(other.0.len()), None);
+        for (c, o) in colors.iter_mut().zip(other.0.iter()) {
+            if c.is_none() && o.is_some() {
+                *c = Some(color);
+            }
+        }
+        Self(colors)
+    }
+}
+
+impl Interpolate for TextColors {
+    fn interp(&self, other: &Self, progress: f32) -> Self {
+        if self.0.len() != other.0.len() {
+            return if progress < 1.0 { self } else { other }.clone();
+        }
+        Self(
+            self.0
+                .iter()
+                .zip(other.0.iter())
+                .map(|(a, b)| match (a, b) {
+                    (Some(a), Some(b)) => Some(a.interp(b, progress)),
+                    _ if progress < 1.0 => *a,
+                    _ => *b,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Colors set by the right-hand side replace those of the left-hand side.
+impl Add for TextColors {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let mut colors = self.0;
+        colors.resize(colors.len().max(other.0.len()), None);
+        for (c, o) in colors.iter_mut().zip(other.0) {
+            if o.is_some() {
+                *c = o;
+            }
+        }
+        Self(colors)
+    }
+}
+
+/// Range of the contours of a glyph within the [Path] of a [Text].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GlyphContours {
+    /// Byte index of the glyph's character in the source text.
+    pub(crate) index: usize,
+    pub(crate) contours: Range<usize>,
+}
+
+/// Contours of every glyph of a [Text] in order, mapping characters of the
+/// text to parts of its [Path].
+#[derive(Debug, Component, Clone, Default)]
+pub struct TextGlyphs(pub(crate) Vec<GlyphContours>);
+
+impl TextGlyphs {
+    /// Whether `path` has the contours of the glyphs. The glyphs of a text
+    /// are dropped once it starts morphing, see
+    /// [forget_morphed_glyphs](crate::system::forget_morphed_glyphs).
+    pub(crate) fn describes(&self, path: &RawPath) -> bool {
+        let contours = path
+            .iter()
+            .filter(|e| matches!(e, PathEvent::Begin { .. }))
+            .count();
+        contours == self.0.last().map_or(0, |g| g.contours.end)
+    }
+
+    /// Split `path`, made of contours like the path of the text, into
+    /// parts of the same color. Parts without a color of their own take the
+    /// colors of the text.
+    pub(crate) fn split_by_color(
+        &self,
+        path: &RawPath,
+        colors: &TextColors,
+    ) -> Vec<(Option<Color>, RawPath)> {
+        let mut parts: Vec<(Option<Color>, _)> = Vec::new();
+        let mut contour = 0;
+        let mut part = 0;
+        for event in path.iter() {
+            if let PathEvent::Begin { .. } = event {
+                let glyph = self.0.partition_point(|g| g.contours.end <= contour);
+                let color = self
+                    .0
+                    .get(glyph)
+                    .filter(|g| g.contours.contains(&contour))
+                    .and_then(|g| colors.get(g.index));
+                part = match parts.iter().position(|(c, _)| *c == color) {
+                    Some(i) => i,
+                    None => {
+                        parts.push((color, RawPath::builder()));
+                        parts.len() - 1
+                    }
+                };
+                contour += 1;
+            }
+            parts[part].1.path_event(event);
+        }
+        parts
+            .into_iter()
+            .map(|(color, builder)| (color, builder.build()))
+            .collect()
+    }
+
+    /// Glyphs of the characters in the byte `range`, describing the path
+    /// made by [extract](TextGlyphs::extract) for the same range. Indices
+    /// start from the start of the range.
+    pub(crate) fn slice(&self, range: &Range<usize>) -> Self {
+        let mut contours = 0;
+        Self(
+            self.0
+                .iter()
+                .filter(|g| range.contains(&g.index))
+                .map(|g| {
+                    let first = contours;
+                    contours += g.contours.len();
+                    GlyphContours {
+                        index: g.index - range.start,
+                        contours: first..contours,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Contours of `path` belonging to the glyphs of the characters in the
+    /// byte `range` of the text.
+    pub(crate) fn extract(&self, path: &RawPath, range: &Range<usize>) -> RawPath {
+        let contours: Vec<&Range<usize>> = self
+            .0
+            .iter()
+            .filter(|g| range.contains(&g.index))
+            .map(|g| &g.contours)
+            .collect();
+        let mut builder = RawPath::builder();
+        let mut contour = 0;
+        let mut inside = false;
+        for event in path.iter() {
+            if let PathEvent::Begin { .. } = event {
+                inside = contours.iter().any(|c| c.contains(&contour));
+                contour += 1;
+            }
+            if inside {
+                builder.path_event(event);
+            }
+        }
+        builder.build()
+    }
+}
+
+/// Fallback chains of fonts for every style used in a text.
+pub(crate) struct TextFonts {
+    /// Chains for each style, starting with the regular style, along with
+    /// whether glyphs must be slanted for lack of an italic font.
+    chains: Vec<(FontStyle, Vec<Font>, bool)>,
+    styles: TextStyles,
+}
+
+impl TextFonts {
+    pub(crate) fn new(fonts: &Fonts, family: &FontFamily, styles: &TextStyles) -> Self {
+        let mut chains = vec![(FontStyle::REGULAR, fonts.chain(family), false)];
+        // Styles only change at the boundaries of the styled ranges.
+        for (range, _) in styles.0.iter() {
+            for style in [styles.at(range.start), styles.at(range.end)] {
+                if !chains.iter().any(|(s, _, _)| *s == style) {
+                    let (chain, slanted) = fonts.chain_styled(family, style);
+                    chains.push((style, chain, slanted));
+                }
+            }
+        }
+        Self {
+            chains,
+            styles: styles.clone(),
+        }
+    }
+    /// Index of the chain used for the character at byte `index`.
+    fn chain_at(&self, index: usize) -> usize {
+        let style = self.styles.at(index);
+        self.chains
+            .iter()
+            .position(|(s, _, _)| *s == style)
+            .unwrap_or(0)
+    }
+}
+
+impl From<Vec<Font>> for TextFonts {
+    fn from(fonts: Vec<Font>) -> Self {
+        Self {
+            chains: vec![(FontStyle::REGULAR, fonts, false)],
+            styles: TextStyles::default(),
+        }
+    }
+}
+
+/// A glyph positioned by [Text::layout].
+///
+/// Positions are in scene units with the y axis pointing up and the
+/// baseline of the first line at zero.
+#[derive(Clone)]
+pub(crate) struct PlacedGlyph {
+    pub(crate) glyph: ScaledGlyph<'static>,
+    pub(crate) position: Point,
+    /// Byte index of the glyph's character in the source text.
+    pub(crate) index: usize,
+    /// Whether the glyph is slanted to look italic.
+    pub(crate) slanted: bool,
+}
+
+/// A character resolved to a glyph of the first font in the chain of its
+/// style that has it.
+struct Shaped {
+    index: usize,
+    ch: char,
+    chain: usize,
+    font: usize,
+    glyph: ScaledGlyph<'static>,
+}
+
+/// Horizontal shear of glyphs slanted to look italic.
+const SLANT: f32 = 0.2;
 
 impl Text {
-    fn path(text: &str, font_size: u32) -> (Path, Size) {
-        use nannou::text;
-        use nannou::{geom::Rect, lyon::geom};
+    /// Place every glyph of `text` according to `layout`.
+    ///
+    /// `font_size` is the height of a line of text (ascent to descent of
+    /// the first font) in scene units. Glyph outlines are scaled directly
+    /// into scene units, so the result doesn't depend on [ZOOM](crate::ZOOM).
+    ///
+    /// `fonts` holds the fallback chain of each style: every character uses
+    /// the first font of the chain for its style containing a glyph for it,
+    /// or the first font if none of them do.
+    pub(crate) fn layout(
+        text: &str,
+        font_size: f32,
+        layout: &TextLayout,
+        fonts: &TextFonts,
+    ) -> Vec<PlacedGlyph> {
+        let scale = nannou::text::Scale::uniform(font_size);
+        let v_metrics = fonts.chains[0].1[0].v_metrics(scale);
+        let line_height =
+            v_metrics.ascent - v_metrics.descent + v_metrics.line_gap + layout.line_spacing;
+        let letter_spacing = layout.letter_spacing;
+        let max_width = layout.max_width;
 
-        let mut builder = Path::builder();
+        let shape = |index: usize, ch: char| {
+            let chain = fonts.chain_at(index);
+            let chain_fonts = &fonts.chains[chain].1;
+            let font = chain_fonts
+                .iter()
+                .position(|f| f.glyph(ch).id() != GlyphId(0))
+                .unwrap_or(0);
+            Shaped {
+                index,
+                ch,
+                chain,
+                font,
+                glyph: chain_fonts[font].glyph(ch).scaled(scale),
+            }
+        };
+        let kerning = |prev: Option<&Shaped>, next: &Shaped| match prev {
+            Some(prev) if prev.chain == next.chain && prev.font == next.font => fonts.chains
+                [next.chain]
+                .1[next.font]
+                .pair_kerning(scale, prev.glyph.id(), next.glyph.id()),
+            _ => 0.0,
+        };
+
+        let mut placed = Vec::new();
+        let mut line_count = 0;
+        let mut offset = 0;
+        for paragraph in text.split('\n') {
+            let chars: Vec<Shaped> = paragraph
+                .trim_end_matches('\r')
+                .char_indices()
+                .map(|(i, ch)| shape(offset + i, ch))
+                .collect();
+            offset += paragraph.len() + 1;
+
+            // Greedily break lines at the last whitespace before exceeding the max width.
+            let mut lines = Vec::new();
+            let mut start = 0;
+            let mut last_break = None;
+            let mut x = 0.0;
+            let mut i = 0;
+            while i < chars.len() {
+                let c = &chars[i];
+                let prev = if i > start { chars.get(i - 1) } else { None };
+                let right = x + kerning(prev, c) + c.glyph.h_metrics().advance_width;
+                if c.ch.is_whitespace() {
+                    last_break = Some(i + 1);
+                } else if let (Some(max), Some(at)) = (max_width, last_break) {
+                    if right > max && at > start {
+                        lines.push(start..at);
+                        start = at;
+                        i = at;
+                        x = 0.0;
+                        last_break = None;
+                        continue;
+                    }
+                }
+                x = right + letter_spacing;
+                i += 1;
+            }
+            lines.push(start..chars.len());
+
+            for range in lines {
+                let line = &chars[range];
+                let mut xs = Vec::with_capacity(line.len());
+                let mut x = 0.0;
+                let mut width = 0.0;
+                for (i, c) in line.iter().enumerate() {
+                    x += kerning(if i > 0 { line.get(i - 1) } else { None }, c);
+                    xs.push(x);
+                    x += c.glyph.h_metrics().advance_width;
+                    if !c.ch.is_whitespace() {
+                        width = x;
+                    }
+                    x += letter_spacing;
+                }
+                let shift = match layout.justify {
+                    Justify::Left => 0.0,
+                    Justify::Center => -width / 2.0,
+                    Justify::Right => -width,
+                };
+                let y = -(line_count as f32) * line_height;
+                for (c, x) in line.iter().zip(xs) {
+                    placed.push(PlacedGlyph {
+                        glyph: c.glyph.clone(),
+                        position: point(x + shift, y),
+                        index: c.index,
+                        slanted: fonts.chains[c.chain].2,
+                    });
+                }
+                line_count += 1;
+            }
+        }
+        placed
+    }
+
+    pub(crate) fn path(
+        text: &str,
+        font_size: f32,
+        layout: &TextLayout,
+        fonts: &TextFonts,
+    ) -> (Path, Size) {
+        let (path, size, _) = Self::outline(text, font_size, layout, fonts);
+        (path, size)
+    }
+
+    /// Path of the whole text centered at the origin, along with its size
+    /// and the contours of each glyph within the path.
+    pub(crate) fn outline(
+        text: &str,
+        font_size: f32,
+        layout: &TextLayout,
+        fonts: &TextFonts,
+    ) -> (Path, Size, TextGlyphs) {
+        use nannou::lyon::algorithms::aabb::bounding_rect;
+        use nannou::lyon::geom;
+        use nannou::lyon::math::Transform as LyonTransform;
+        use nannou::text::glyph;
 
-        let rect = nannou::geom::Rect::from_w_h(1.0, 1.0);
-        let text = text::text(text)
-            .font_size(font_size)
-            .no_line_wrap()
-            .left_justify()
-            .build(rect);
+        let mut builder = Path::builder();
+        let mut glyphs = Vec::new();
+        let mut contours = 0;
+        for g in Self::layout(text, font_size, layout, fonts) {
+            let slant = if g.slanted { SLANT } else { 0.0 };
+            let transform = LyonTransform::new(1.0, 0.0, slant, 1.0, g.position.x, g.position.y);
+            let first = contours;
+            for e in glyph::path_events(g.glyph).into_iter().flatten() {
+                if let PathEvent::Begin { .. } = e {
+                    contours += 1;
+                }
+                builder.path_event(e.transformed(&transform));
+            }
+            glyphs.push(GlyphContours {
+                index: g.index,
+                contours: first..contours,
+            });
+        }
+        let raw = builder.build();
+        let glyphs = TextGlyphs(glyphs);
 
-        for e in text.path_events() {
-            builder.path_event(e);
+        if raw.iter().next().is_none() {
+            return (Path::new(raw, true), Size::ZERO, glyphs);
         }
 
-        let rect = text.bounding_rect();
-        let x = -rect.x();
-        let y = -rect.y();
-        // let scale = 1.0 / rect.w().max(rect.h()) * width;
-        let scale = 0.01;
+        let rect = bounding_rect(raw.iter());
+        let center = rect.center();
 
         (
             Path::new(
-                builder
-                    .build()
-                    .transformed(&geom::Transform::translation(x, y).then_scale(scale, scale)),
+                raw.transformed(&geom::Translation::new(-center.x, -center.y)),
                 true,
             ),
-            Size::from(rect.w(), rect.h()),
+            Size::from(rect.width(), rect.height()),
+            glyphs,
         )
     }
 }
 
 pub struct TextBuilder<'a> {
     text: String,
-    font_size: u32,
+    font_size: f32,
+    layout: TextLayout,
+    family: FontFamily,
+    styles: TextStyles,
+    spans: Vec<(Range<usize>, Color)>,
     stroke_weight: StrokeWeight,
     stroke_color: Color,
     fill_color: Color,
@@ -55,7 +504,11 @@ impl<'a> TextBuilder<'a> {
         let fill_color = Color::random();
         Self {
             text: String::new(),
-            font_size: 30,
+            font_size: 0.4,
+            layout: Default::default(),
+            family: Default::default(),
+            styles: Default::default(),
+            spans: Vec::new(),
             stroke_weight: StrokeWeight::THIN,
             fill_color,
             stroke_color: fill_color.brighten(),
@@ -73,10 +526,99 @@ impl<'a> TextBuilder<'a> {
         self.stroke_color = color.brighten();
         self
     }
-    pub fn with_font_size(mut self, size: u32) -> Self {
+    /// Color the characters in the byte `range` of the text, e.g. `3..7`,
+    /// instead of the color of the whole text.
+    pub fn with_span(mut self, range: Range<usize>, color: Color) -> Self {
+        self.spans.push((range, color));
+        self
+    }
+    /// Set the characters in the byte `range` in bold, using the variant of
+    /// the font registered with [Fonts::set_variant]. Without one, the
+    /// regular font is used.
+    pub fn with_bold(mut self, range: Range<usize>) -> Self {
+        self.styles.0.push((range, FontStyle::BOLD));
+        self
+    }
+    /// Set the characters in the byte `range` in italic, using the variant
+    /// of the font registered with [Fonts::set_variant]. Without one, the
+    /// glyphs are slanted instead.
+    pub fn with_italic(mut self, range: Range<usize>) -> Self {
+        self.styles.0.push((range, FontStyle::ITALIC));
+        self
+    }
+    /// Height of a line of text in scene units.
+    pub fn with_font_size(mut self, size: f32) -> Self {
         self.font_size = size;
         self
     }
+    /// Use a font registered in the scene's [Fonts].
+    pub fn with_font(mut self, font: FontId) -> Self {
+        self.family = FontFamily::new(font);
+        self
+    }
+    /// Load a font file into the scene's [Fonts] and use it for this text.
+    ///
+    /// Panics if the file can't be loaded. Use [Scene::load_font_file] to
+    /// handle the error instead.
+    pub fn with_font_file(self, path: impl AsRef<FilePath>) -> Self {
+        let font = self
+            .scene
+            .load_font_file(path)
+            .expect("Failed to load font file");
+        self.with_font(font)
+    }
+    /// Load a font from raw bytes into the scene's [Fonts] and use it for this text.
+    ///
+    /// Panics if the bytes don't contain a valid font. Use
+    /// [Scene::load_font_bytes] to handle the error instead.
+    pub fn with_font_bytes(self, bytes: &[u8]) -> Self {
+        let font = self
+            .scene
+            .load_font_bytes(bytes)
+            .expect("Failed to load font from bytes");
+        self.with_font(font)
+    }
+    /// Font to use for characters missing from the previously added fonts.
+    pub fn with_fallback_font(mut self, font: FontId) -> Self {
+        self.family.add(font);
+        self
+    }
+    /// Append a new line of text below the existing text.
+    pub fn add_line(mut self, line: &str) -> Self {
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.text.push_str(line);
+        self
+    }
+    /// Wrap lines by word once they exceed the given width.
+    pub fn with_max_width(mut self, width: f32) -> Self {
+        self.layout.max_width = Some(width);
+        self
+    }
+    pub fn with_justify(mut self, justify: Justify) -> Self {
+        self.layout.justify = justify;
+        self
+    }
+    pub fn left_justify(self) -> Self {
+        self.with_justify(Justify::Left)
+    }
+    pub fn center_justify(self) -> Self {
+        self.with_justify(Justify::Center)
+    }
+    pub fn right_justify(self) -> Self {
+        self.with_justify(Justify::Right)
+    }
+    /// Additional space between lines.
+    pub fn with_line_spacing(mut self, spacing: f32) -> Self {
+        self.layout.line_spacing = spacing;
+        self
+    }
+    /// Additional space between characters.
+    pub fn with_letter_spacing(mut self, spacing: f32) -> Self {
+        self.layout.letter_spacing = spacing;
+        self
+    }
 }
 
 crate::stroke_builder!(TextBuilder);
@@ -92,7 +634,13 @@ impl Create<TextId> for TextBuilder<'_> {
         let world = self.scene.world.get_mut();
         let position = self.position;
         let scale = Scale::ONE;
-        let (path, size) = Text::path(&self.text, self.font_size);
+        let fonts = TextFonts::new(world.resource::<Fonts>(), &self.family, &self.styles);
+        let (path, size, glyphs) = Text::outline(&self.text, self.font_size, &self.layout, &fonts);
+        let mut colors = TextColors(vec![None; self.text.len()]);
+        for (range, color) in self.spans.iter() {
+            colors =
+                colors + TextColors::range(range.start..range.end.min(self.text.len()), *color);
+        }
         let transform = Transform::identity()
             .scale(scale)
             .rotate(self.angle)
@@ -105,9 +653,15 @@ impl Create<TextId> for TextBuilder<'_> {
         );
         let id = world
             .spawn_empty()
-            .insert(Text)
+            .insert(Text(self.text.clone()))
             .insert(FontSize(self.font_size))
+            .insert(self.layout)
+            .insert(self.family.clone())
+            .insert(self.styles.clone())
+            .insert(glyphs)
+            .insert(colors)
             .insert(size)
+            .insert(BoundingSize(size))
             .insert(scale)
             .insert(self.position)
             .insert(self.angle)
@@ -143,3 +697,386 @@ impl WithPath for TextId {}
 impl WithPosition for TextId {}
 impl WithAngle for TextId {}
 impl WithSize for TextId {}
+impl WithTextColor for TextId {}
+
+impl TextId {
+    /// Split the text into one object per character, grouped under an
+    /// [Empty](crate::Empty) object. Whitespace is skipped.
+    ///
+    /// The objects have the exact outlines, layout and colors of the
+    /// characters, but start hidden like any new object, and the text itself
+    /// is left unchanged. To swap the text for its characters, fade out the
+    /// text and fade in the characters with a `run_time` of zero.
+    ///
+    /// A text morphed into another shape has no characters left, and gives
+    /// no parts.
+    pub fn chars(&self, scene: &mut Scene) -> TextPartsId {
+        let ranges = match scene.world.borrow().get::<Text>(self.0) {
+            Some(text) => text
+                .0
+                .char_indices()
+                .filter(|(_, ch)| !ch.is_whitespace())
+                .map(|(i, ch)| i..i + ch.len_utf8())
+                .collect(),
+            None => Vec::new(),
+        };
+        self.split(scene, ranges)
+    }
+
+    /// Split the text into one object per word, i.e. per run of
+    /// non-whitespace characters, as [chars](TextId::chars) does for
+    /// characters.
+    pub fn words(&self, scene: &mut Scene) -> TextPartsId {
+        let ranges = match scene.world.borrow().get::<Text>(self.0) {
+            Some(text) => {
+                let mut ranges: Vec<Range<usize>> = Vec::new();
+                let mut in_word = false;
+                for (i, ch) in text.0.char_indices() {
+                    let end = i + ch.len_utf8();
+                    match ranges.last_mut() {
+                        _ if ch.is_whitespace() => in_word = false,
+                        Some(last) if in_word => last.end = end,
+                        _ => {
+                            ranges.push(i..end);
+                            in_word = true;
+                        }
+                    }
+                }
+                ranges
+            }
+            None => Vec::new(),
+        };
+        self.split(scene, ranges)
+    }
+
+    /// One object for each byte range of the text, placed where the range
+    /// appears in the text.
+    fn split(&self, scene: &mut Scene, ranges: Vec<Range<usize>>) -> TextPartsId {
+        use nannou::lyon::algorithms::aabb::bounding_rect;
+        use nannou::lyon::geom::Translation;
+        use nannou::lyon::math::Transform as LyonTransform;
+
+        let world = scene.world.get_mut();
+        let (path, glyphs) = match (world.get::<Path>(self.0), world.get::<TextGlyphs>(self.0)) {
+            (Some(path), Some(glyphs)) if glyphs.describes(&path.raw) => (path, glyphs),
+            // The characters of a text morphed into another shape are lost.
+            _ => {
+                return TextPartsId {
+                    group: crate::empty(scene).make(),
+                    parts: Vec::new(),
+                }
+            }
+        };
+        let colors = world.get::<TextColors>(self.0).cloned().unwrap_or_default();
+        let fill = world.get::<FillColor>(self.0).map_or(Color::WHITE, |c| c.0);
+        let stroke = world.get::<StrokeColor>(self.0).map_or(fill, |c| c.0);
+        let stroke_weight = world
+            .get::<StrokeWeight>(self.0)
+            .copied()
+            .unwrap_or_default();
+        let position = world.get::<Position>(self.0).copied().unwrap_or_default();
+        let angle = world.get::<Angle>(self.0).copied().unwrap_or_default();
+        let scale = world.get::<Scale>(self.0).copied().unwrap_or(Scale::ONE);
+        let size = world.get::<Size>(self.0).copied().unwrap_or(Size::ZERO);
+
+        let pieces: Vec<_> = ranges
+            .into_iter()
+            .map(|range| {
+                let raw = glyphs
+                    .extract(&path.raw, &range)
+                    .transformed(&LyonTransform::scale(scale.x, scale.y));
+                let part_glyphs = glyphs.slice(&range);
+                let part_colors = colors.slice(&range);
+                let mut used = part_glyphs
+                    .0
+                    .iter()
+                    .filter(|g| !g.contours.is_empty())
+                    .map(|g| part_colors.get(g.index));
+                let color = used.next().flatten();
+                // Parts spanning several colors are drawn like the text,
+                // with the colors of their characters.
+                let mixed = if used.all(|c| c == color) {
+                    None
+                } else {
+                    Some((part_glyphs, part_colors))
+                };
+                (range, raw, color, mixed)
+            })
+            .filter(|(_, raw, _, _)| raw.iter().next().is_some())
+            .collect();
+
+        let (sin, cos) = angle.0.sin_cos();
+        let mut parts = Vec::with_capacity(pieces.len());
+        for (range, raw, color, mixed) in pieces {
+            let center = bounding_rect(raw.iter()).center();
+            let (fill, stroke) = match color {
+                Some(color) if mixed.is_none() => (color, color.brighten()),
+                _ => (fill, stroke),
+            };
+            let id = super::path::insert_path(
+                scene,
+                Path::new(
+                    raw.transformed(&Translation::new(-center.x, -center.y)),
+                    true,
+                ),
+                Position {
+                    x: position.x + center.x * cos - center.y * sin,
+                    y: position.y + center.x * sin + center.y * cos,
+                },
+                angle,
+                stroke_weight,
+                stroke,
+                Some(fill),
+            );
+            if let Some(components) = mixed {
+                scene.world.get_mut().entity_mut(id.0).insert(components);
+            }
+            parts.push((range, id));
+        }
+
+        let mut group = crate::empty(scene)
+            .with_position(position.x, position.y)
+            .with_angle(angle.0)
+            .with_size(size.width * scale.x, size.height * scale.y);
+        for (_, id) in parts.iter() {
+            group = group.add(*id);
+        }
+        TextPartsId {
+            group: group.make(),
+            parts,
+        }
+    }
+}
+
+/// Characters or words of a text made with [TextId::chars] or
+/// [TextId::words], from first to last.
+#[derive(Debug, Clone)]
+pub struct TextPartsId {
+    group: EmptyId,
+    /// Objects along with the byte range of the text they show.
+    parts: Vec<(Range<usize>, PathId)>,
+}
+
+impl TextPartsId {
+    /// [Empty](crate::Empty) object holding all the parts as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn parts(&self) -> Vec<PathId> {
+        self.parts.iter().map(|(_, id)| *id).collect()
+    }
+    pub fn get(&self, index: usize) -> Option<PathId> {
+        self.parts.get(index).map(|(_, id)| *id)
+    }
+    /// Byte range of the text shown by the part at `index`.
+    pub fn range(&self, index: usize) -> Option<Range<usize>> {
+        self.parts.get(index).map(|(range, _)| range.clone())
+    }
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.parts
+            .iter()
+            .map(|(_, id)| id.show_creation())
+            .collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.parts.iter().map(|(_, id)| id.fade_in()).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.parts.iter().map(|(_, id)| id.fade_out()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fonts() -> TextFonts {
+        Fonts::new().chain(&FontFamily::default()).into()
+    }
+
+    #[test]
+    fn wrapped_text_is_taller() {
+        let text = "The quick brown fox jumps over the lazy dog";
+        let (_, single) = Text::path(text, 0.4, &TextLayout::default(), &fonts());
+        let layout = TextLayout {
+            max_width: Some(single.width / 3.0),
+            ..Default::default()
+        };
+        let (_, wrapped) = Text::path(text, 0.4, &layout, &fonts());
+        assert!(wrapped.width < single.width);
+        assert!(wrapped.height > 2.0 * single.height);
+    }
+
+    #[test]
+    fn letter_spacing_widens_text() {
+        let (_, normal) = Text::path("Hello", 0.4, &TextLayout::default(), &fonts());
+        let layout = TextLayout {
+            letter_spacing: 0.1,
+            ..Default::default()
+        };
+        let (_, spaced) = Text::path("Hello", 0.4, &layout, &fonts());
+        assert!((spaced.width - normal.width - 0.4).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn font_size_is_in_scene_units() {
+        let (_, small) = Text::path("Hello", 0.4, &TextLayout::default(), &fonts());
+        let (_, large) = Text::path("Hello", 0.8, &TextLayout::default(), &fonts());
+        assert!(small.height < 0.4);
+        assert!((large.width - 2.0 * small.width).abs() < 1.0e-3);
+        assert!((large.height - 2.0 * small.height).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn newlines_start_new_lines() {
+        let glyphs = Text::layout("ab\ncd", 0.4, &TextLayout::default(), &fonts());
+        let indices: Vec<usize> = glyphs.iter().map(|g| g.index).collect();
+        assert_eq!(indices, vec![0, 1, 3, 4]);
+        assert_eq!(glyphs[0].position.y, glyphs[1].position.y);
+        assert!(glyphs[2].position.y < glyphs[0].position.y);
+        assert_eq!(glyphs[0].position.x, glyphs[2].position.x);
+    }
+
+    #[test]
+    fn colors_apply_to_glyph_contours() {
+        let (path, _, glyphs) = Text::outline("ab c", 0.4, &TextLayout::default(), &fonts());
+        assert_eq!(glyphs.0.len(), 4);
+        assert_eq!(glyphs.0[3].index, 3);
+        // The space has no contours.
+        assert!(glyphs.0[2].contours.is_empty());
+
+        let colors = TextColors(vec![None; 4]) + TextColors::range(1..2, Color::RED);
+        assert_eq!(colors.get(1), Some(Color::RED));
+        assert_eq!(colors.get(3), None);
+        let parts = glyphs.split_by_color(&path.raw, &colors);
+        // Parts are grouped by color, so `a` and `c` share a part.
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1].0, Some(Color::RED));
+
+        let begin = TextColors(vec![None; 4]).filled(&colors, Color::BLUE);
+        let halfway = begin.interp(&colors, 0.5).get(1).unwrap();
+        assert!((halfway.red - 0.5).abs() < EPS && (halfway.blue - 0.5).abs() < EPS);
+    }
+
+    #[test]
+    fn extract_keeps_contours_of_range() {
+        let (path, _, glyphs) = Text::outline("ab c", 0.4, &TextLayout::default(), &fonts());
+        let begins = |raw: &RawPath| {
+            raw.iter()
+                .filter(|e| matches!(e, PathEvent::Begin { .. }))
+                .count()
+        };
+        let contours = |range: Range<usize>| -> usize {
+            glyphs.0[range].iter().map(|g| g.contours.len()).sum()
+        };
+        assert_eq!(begins(&glyphs.extract(&path.raw, &(0..2))), contours(0..2));
+        assert_eq!(begins(&glyphs.extract(&path.raw, &(3..4))), contours(3..4));
+        assert_eq!(
+            begins(&glyphs.extract(&path.raw, &(0..4))),
+            begins(&path.raw)
+        );
+        assert_eq!(begins(&glyphs.extract(&path.raw, &(2..3))), 0);
+    }
+
+    #[test]
+    fn morphed_text_is_not_split_by_color() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let text = crate::text(&mut scene).with_text("ab").make();
+        let circle = crate::circle(&mut scene).make();
+        let describes = |scene: &mut Scene| {
+            let world = scene.world.get_mut();
+            let glyphs = world.get::<TextGlyphs>(text.0).unwrap();
+            glyphs.describes(&world.get::<Path>(text.0).unwrap().raw)
+        };
+        assert!(describes(&mut scene));
+
+        // Partially drawn texts keep their glyphs.
+        let rect = nannou::geom::Rect::from_w_h(640.0, 480.0);
+        scene.play(text.show_creation()).run_time(1.0);
+        scene.update(1.0, rect);
+        assert!(describes(&mut scene));
+
+        scene.play(text.morph(circle)).run_time(1.0);
+        for i in 0..=10 {
+            scene.update(1.5 + i as f32 * 0.1, rect);
+        }
+        assert!(!describes(&mut scene));
+    }
+
+    #[test]
+    fn parts_keep_colors_of_their_characters() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let text = crate::text(&mut scene)
+            .with_text("ab cd")
+            .with_span(1..2, Color::RED)
+            .make();
+
+        let words = text.words(&mut scene);
+        assert_eq!(words.len(), 2);
+        let world = scene.world.get_mut();
+        // The first word spans two colors, and is split by color when drawn.
+        let mixed = words.get(0).unwrap().0;
+        let glyphs = world.get::<TextGlyphs>(mixed).unwrap();
+        assert!(glyphs.describes(&world.get::<Path>(mixed).unwrap().raw));
+        let colors = world.get::<TextColors>(mixed).unwrap();
+        assert_eq!((colors.get(0), colors.get(1)), (None, Some(Color::RED)));
+        assert!(world.get::<TextGlyphs>(words.get(1).unwrap().0).is_none());
+
+        let chars = text.chars(&mut scene);
+        let world = scene.world.get_mut();
+        let fill = |id: PathId| world.get::<FillColor>(id.0).unwrap().0;
+        assert_eq!(fill(chars.get(1).unwrap()), Color::RED);
+        assert_ne!(fill(chars.get(0).unwrap()), Color::RED);
+    }
+
+    #[test]
+    fn morphed_text_has_no_parts() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let text = crate::text(&mut scene).with_text("ab").make();
+        let circle = crate::circle(&mut scene).make();
+        scene.play(text.morph(circle)).run_time(1.0);
+        let rect = nannou::geom::Rect::from_w_h(640.0, 480.0);
+        for i in 0..=10 {
+            scene.update(0.5 + i as f32 * 0.1, rect);
+        }
+        assert!(text.chars(&mut scene).is_empty());
+    }
+
+    #[test]
+    fn morph_into_same_contour_count_drops_colors() {
+        let mut scene = Scene::new(nannou::geom::Rect::from_w_h(640.0, 480.0));
+        let text = crate::text(&mut scene)
+            .with_text("ab")
+            .with_span(0..1, Color::RED)
+            .make();
+        let other = crate::text(&mut scene).with_text("ba").make();
+        let contours = |scene: &mut Scene, entity: Entity| {
+            let world = scene.world.get_mut();
+            world
+                .get::<Path>(entity)
+                .unwrap()
+                .raw
+                .iter()
+                .filter(|e| matches!(e, PathEvent::Begin { .. }))
+                .count()
+        };
+        assert_eq!(contours(&mut scene, text.0), contours(&mut scene, other.0));
+
+        scene.play(text.morph(other)).run_time(1.0);
+        let rect = nannou::geom::Rect::from_w_h(640.0, 480.0);
+        for i in 0..=10 {
+            scene.update(0.5 + i as f32 * 0.1, rect);
+        }
+        let world = scene.world.get_mut();
+        let glyphs = world.get::<TextGlyphs>(text.0).unwrap();
+        assert!(!glyphs.describes(&world.get::<Path>(text.0).unwrap().raw));
+        assert_eq!(world.get::<TextColors>(text.0).unwrap().get(0), None);
+        assert!(text.words(&mut scene).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use super::common::*;
+
+/// Component indicating a triangle.
+///
+/// The vertices are stored relative to the centroid of the triangle, so that
+/// the triangle rotates around its centroid. When the [Size] of a triangle
+/// changes, the vertices are stretched to fit the new size.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Triangle {
+    vertices: [Point; 3],
+}
+
+impl Triangle {
+    /// Create an equilateral triangle with given side length, pointing up.
+    pub fn equilateral(side: f32) -> Self {
+        let height = side * 3.0_f32.sqrt() / 2.0;
+        Self::from_points(
+            point(-side / 2.0, -height / 3.0),
+            point(side / 2.0, -height / 3.0),
+            point(0.0, height * 2.0 / 3.0),
+        )
+    }
+
+    /// Create a triangle from arbitrary vertices. The vertices are translated
+    /// so that they are centered around their centroid.
+    pub fn from_points(p1: Point, p2: Point, p3: Point) -> Self {
+        let centroid = Position::from_points(&[p1, p2, p3]);
+        let offset = |p: Point| point(p.x - centroid.x, p.y - centroid.y);
+        Self {
+            vertices: [offset(p1), offset(p2), offset(p3)],
+        }
+    }
+
+    fn size(&self) -> Size {
+        Size::from_points(&self.vertices)
+    }
+}
+
+impl PathComponent for Triangle {
+    fn path(&self, size: &Size) -> Path {
+        let (sx, sy) = self.size().scale_factor(size);
+        let scale = |p: Point| point(p.x * sx, p.y * sy);
+        let [p1, p2, p3] = self.vertices;
+
+        let mut builder = Path::svg_builder();
+        builder.move_to(scale(p1));
+        builder.line_to(scale(p2));
+        builder.line_to(scale(p3));
+        builder.close();
+
+        Path::new(builder.build(), true)
+    }
+}
+
+pub struct TriangleBuilder<'a> {
+    triangle: Triangle,
+    stroke_weight: StrokeWeight,
+    stroke_color: Color,
+    fill_color: Color,
+    position: Position,
+    angle: Angle,
+    scene: &'a mut Scene,
+}
+
+impl<'a> TriangleBuilder<'a> {
+    fn new(scene: &'a mut Scene) -> Self {
+        let fill_color = Color::random();
+        Self {
+            triangle: Triangle::equilateral(1.0),
+            stroke_weight: StrokeWeight::THICK,
+            fill_color,
+            stroke_color: fill_color.brighten(),
+            position: Default::default(),
+            angle: Default::default(),
+            scene,
+        }
+    }
+    /// Make an equilateral triangle with given side length.
+    pub fn with_side(mut self, side: f32) -> Self {
+        self.triangle = Triangle::equilateral(side);
+        self
+    }
+    /// Make a triangle from three arbitrary vertices. The triangle is
+    /// positioned at the centroid of the given vertices.
+    pub fn with_points(mut self, p1: Point, p2: Point, p3: Point) -> Self {
+        self.triangle = Triangle::from_points(p1, p2, p3);
+        self.position = Position::from_points(&[p1, p2, p3]);
+        self
+    }
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self.stroke_color = color.brighten();
+        self
+    }
+}
+
+crate::angle_builder!(TriangleBuilder);
+crate::stroke_builder!(TriangleBuilder);
+crate::position_builder!(TriangleBuilder);
+crate::fill_builder!(TriangleBuilder);
+
+impl Create<TriangleId> for TriangleBuilder<'_> {
+    fn scene_mut(&mut self) -> &mut Scene {
+        self.scene
+    }
+    fn make(&mut self) -> TriangleId {
+        let depth = self.scene.increment_counter();
+        let world = self.scene.world.get_mut();
+        let scale = Scale::ONE;
+        let size = self.triangle.size();
+        let path = self.triangle.path(&size);
+        let transform = Transform::identity()
+            .scale(scale)
+            .rotate(self.angle)
+            .translate(self.position.into());
+        let screen_transform = self.scene.transform;
+
+        let global_path = PixelPath(
+            path.clone()
+                .transform(&transform.transform(screen_transform)),
+        );
+
+        let id = world
+            .spawn_empty()
+            .insert(self.triangle)
+            .insert(size)
+            .insert(BoundingSize(size))
+            .insert(scale)
+            .insert(self.position)
+            .insert(self.angle)
+            .insert(self.stroke_weight)
+            .insert(StrokeColor(self.stroke_color))
+            .insert(FillColor(self.fill_color))
+            .insert(Opacity(0.0))
+            .insert(depth)
+            .insert(PathCompletion(0.0))
+            .insert(path)
+            .insert(global_path)
+            .insert(transform)
+            .insert(HasFill(true))
+            .id();
+
+        id.into()
+    }
+}
+
+pub fn triangle(scene: &mut Scene) -> TriangleBuilder<'_> {
+    TriangleBuilder::new(scene)
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct TriangleId(pub(crate) Entity);
+crate::into_entity!(TriangleId);
+
+impl WithStroke for TriangleId {}
+impl WithFill for TriangleId {}
+impl WithColor for TriangleId {}
+impl WithPath for TriangleId {}
+impl WithPosition for TriangleId {}
+impl WithAngle for TriangleId {}
+impl WithSize for TriangleId {}
+impl WithStrokeWeight for TriangleId {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equilateral_is_centered_on_centroid() {
+        let triangle = Triangle::equilateral(1.0);
+        let size = triangle.size();
+        assert!((size.width - 1.0).abs() < 1.0e-6);
+        assert!((size.height - 3.0_f32.sqrt() / 2.0).abs() < 1.0e-6);
+        let sum = triangle
+            .vertices
+            .iter()
+            .fold(point(0.0, 0.0), |a, p| point(a.x + p.x, a.y + p.y));
+        assert!(sum.x.abs() < 1.0e-6 && sum.y.abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn path_is_stretched_to_size() {
+        let triangle = Triangle::from_points(point(1.0, 1.0), point(3.0, 1.0), point(1.0, 4.0));
+        let size = triangle.path(&triangle.size()).size();
+        assert!((size.width - 2.0).abs() < 1.0e-3);
+        assert!((size.height - 3.0).abs() < 1.0e-3);
+
+        let path = triangle.path(&Size::from(4.0, 1.5));
+        assert!(path.closed);
+        let size = path.size();
+        assert!((size.width - 4.0).abs() < 1.0e-3);
+        assert!((size.height - 1.5).abs() < 1.0e-3);
+    }
+}
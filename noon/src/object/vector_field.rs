@@ -0,0 +1,400 @@
+use super::common::*;
+use crate::{ArrowId, EmptyId, Interpolate, LineId, Vector};
+use nannou::geom::Rect;
+
+/// Colors used by default to show the magnitude of a field, from the
+/// smallest to the largest.
+fn default_colors() -> Vec<Color> {
+    vec![
+        Color::new(0.23, 0.51, 0.96),
+        Color::new(0.35, 0.80, 0.45),
+        Color::new(0.98, 0.85, 0.25),
+        Color::new(0.94, 0.33, 0.24),
+    ]
+}
+
+/// Color at `t` in `0.0..=1.0` along a gradient going through `colors`.
+pub(crate) fn gradient(colors: &[Color], t: f32) -> Color {
+    match colors.len() {
+        0 => Color::WHITE,
+        1 => colors[0],
+        n => {
+            let t = t.clamp(0.0, 1.0) * (n - 1) as f32;
+            let i = (t.floor() as usize).min(n - 2);
+            colors[i].interp(&colors[i + 1], t - i as f32)
+        }
+    }
+}
+
+/// Points of a grid with the given spacing, centered within the region.
+fn grid(region: &Rect, spacing: f32) -> Vec<Point> {
+    if spacing <= 0.0 {
+        return Vec::new();
+    }
+    let nx = (region.w() / spacing + EPS).floor() as usize;
+    let ny = (region.h() / spacing + EPS).floor() as usize;
+    let x0 = region.x() - nx as f32 * spacing / 2.0;
+    let y0 = region.y() - ny as f32 * spacing / 2.0;
+    (0..=ny)
+        .flat_map(|j| {
+            (0..=nx).map(move |i| point(x0 + i as f32 * spacing, y0 + j as f32 * spacing))
+        })
+        .collect()
+}
+
+fn contains(region: &Rect, p: Point) -> bool {
+    p.x >= region.left() - EPS
+        && p.x <= region.right() + EPS
+        && p.y >= region.bottom() - EPS
+        && p.y <= region.top() + EPS
+}
+
+/// Parameter in `0.0..=1.0` of `magnitude` between `min` and `max`.
+fn normalize(magnitude: f32, min: f32, max: f32) -> f32 {
+    if max - min > EPS {
+        (magnitude - min) / (max - min)
+    } else {
+        0.5
+    }
+}
+
+/// Trajectory of a particle starting at `start` and moving with the velocity
+/// given by `field`, integrated with the classical Runge-Kutta method.
+///
+/// Integration stops when the particle leaves the region, comes to rest, or
+/// after `max_steps` steps.
+pub(crate) fn integrate(
+    field: &dyn Fn(Point) -> Vector,
+    start: Point,
+    region: &Rect,
+    step: f32,
+    max_steps: usize,
+) -> Vec<Point> {
+    let mut points = vec![start];
+    let mut p = start;
+    for _ in 0..max_steps {
+        let k1 = field(p);
+        if k1.length() < EPS {
+            break;
+        }
+        let k2 = field(p + k1 * step / 2.0);
+        let k3 = field(p + k2 * step / 2.0);
+        let k4 = field(p + k3 * step);
+        p += (k1 + k2 * 2.0 + k3 * 2.0 + k4) * step / 6.0;
+        if !p.x.is_finite() || !p.y.is_finite() || !contains(region, p) {
+            break;
+        }
+        points.push(p);
+    }
+    points
+}
+
+/// Builder for a grid of arrows showing a vector field.
+///
+/// Each arrow is centered on its grid point and colored according to the
+/// magnitude of the field there. Arrows are scaled by `length_scale`, but
+/// never grow longer than the grid spacing.
+pub struct VectorFieldBuilder<'a> {
+    field: Box<dyn Fn(Point) -> Vector + 'a>,
+    region: Rect,
+    spacing: f32,
+    length_scale: f32,
+    colors: Vec<Color>,
+    stroke_weight: StrokeWeight,
+    scene: &'a mut Scene,
+}
+
+impl<'a> VectorFieldBuilder<'a> {
+    fn new(
+        scene: &'a mut Scene,
+        field: impl Fn(Point) -> Vector + 'a,
+        region: Rect,
+        spacing: f32,
+    ) -> Self {
+        Self {
+            field: Box::new(field),
+            region,
+            spacing,
+            length_scale: 1.0,
+            colors: default_colors(),
+            stroke_weight: StrokeWeight(2.0),
+            scene,
+        }
+    }
+    /// Length in scene units of an arrow for a vector of unit magnitude.
+    pub fn with_length_scale(mut self, scale: f32) -> Self {
+        self.length_scale = scale;
+        self
+    }
+    /// Colors for increasing magnitudes, from the smallest to the largest
+    /// magnitude found in the region.
+    pub fn with_colors(mut self, colors: &[Color]) -> Self {
+        self.colors = colors.to_vec();
+        self
+    }
+    /// Draw every arrow with the same color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.colors = vec![color];
+        self
+    }
+    pub fn with_stroke_weight(mut self, weight: f32) -> Self {
+        self.stroke_weight = StrokeWeight(weight);
+        self
+    }
+
+    pub fn make(&mut self) -> VectorFieldId {
+        let vectors: Vec<(Point, Vector)> = grid(&self.region, self.spacing)
+            .into_iter()
+            .map(|p| (p, (self.field)(p)))
+            .filter(|(_, v)| v.x.is_finite() && v.y.is_finite() && v.length() > EPS)
+            .collect();
+        let (min, max) = vectors
+            .iter()
+            .map(|(_, v)| v.length())
+            .fold((f32::MAX, 0.0_f32), |(min, max), m| {
+                (min.min(m), max.max(m))
+            });
+
+        let max_length = 0.8 * self.spacing;
+        let mut arrows = Vec::new();
+        for (p, v) in vectors.iter() {
+            let length = (v.length() * self.length_scale).min(max_length);
+            let half = v.normalize() * length / 2.0;
+            let arrow = crate::arrow(self.scene)
+                .from(p.x - half.x, p.y - half.y)
+                .to(p.x + half.x, p.y + half.y)
+                .with_tip_size(0.25 * self.spacing, 0.2 * self.spacing)
+                .with_color(gradient(&self.colors, normalize(v.length(), min, max)))
+                .with_stroke_weight(self.stroke_weight.0)
+                .make();
+            arrows.push(arrow);
+        }
+
+        let mut group = crate::empty(self.scene);
+        for arrow in arrows.iter() {
+            group = group.add(*arrow);
+        }
+        let group = group.with_position(self.region.x(), self.region.y()).make();
+        VectorFieldId { group, arrows }
+    }
+}
+
+pub fn vector_field<'a>(
+    scene: &'a mut Scene,
+    field: impl Fn(Point) -> Vector + 'a,
+    region: Rect,
+    spacing: f32,
+) -> VectorFieldBuilder<'a> {
+    VectorFieldBuilder::new(scene, field, region, spacing)
+}
+
+/// Grid of arrows showing a vector field.
+#[derive(Debug, Clone)]
+pub struct VectorFieldId {
+    group: EmptyId,
+    arrows: Vec<ArrowId>,
+}
+
+impl VectorFieldId {
+    /// [Empty](crate::Empty) object holding the arrows as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn arrows(&self) -> &[ArrowId] {
+        &self.arrows
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.arrows.iter().map(|a| a.show_creation()).collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.arrows.iter().map(|a| a.fade_in()).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.arrows.iter().map(|a| a.fade_out()).collect()
+    }
+}
+
+/// Builder for stream lines of a vector field.
+///
+/// A trajectory is integrated from every point of a grid covering the
+/// region, following the field until it leaves the region, and drawn as a
+/// line colored by the average magnitude of the field along it.
+pub struct StreamLinesBuilder<'a> {
+    field: Box<dyn Fn(Point) -> Vector + 'a>,
+    region: Rect,
+    spacing: f32,
+    step: f32,
+    max_steps: usize,
+    colors: Vec<Color>,
+    stroke_weight: StrokeWeight,
+    scene: &'a mut Scene,
+}
+
+impl<'a> StreamLinesBuilder<'a> {
+    fn new(
+        scene: &'a mut Scene,
+        field: impl Fn(Point) -> Vector + 'a,
+        region: Rect,
+        spacing: f32,
+    ) -> Self {
+        Self {
+            field: Box::new(field),
+            region,
+            spacing,
+            step: 0.05,
+            max_steps: 100,
+            colors: default_colors(),
+            stroke_weight: StrokeWeight(2.0),
+            scene,
+        }
+    }
+    /// Time step of the integration.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+    /// Maximum number of integration steps for each line.
+    pub fn with_max_steps(mut self, steps: usize) -> Self {
+        self.max_steps = steps;
+        self
+    }
+    /// Colors for increasing magnitudes, from the smallest to the largest
+    /// average magnitude of the lines.
+    pub fn with_colors(mut self, colors: &[Color]) -> Self {
+        self.colors = colors.to_vec();
+        self
+    }
+    /// Draw every line with the same color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.colors = vec![color];
+        self
+    }
+    pub fn with_stroke_weight(mut self, weight: f32) -> Self {
+        self.stroke_weight = StrokeWeight(weight);
+        self
+    }
+
+    pub fn make(&mut self) -> StreamLinesId {
+        let trajectories: Vec<(Vec<Point>, f32)> = grid(&self.region, self.spacing)
+            .into_iter()
+            .map(|start| integrate(&*self.field, start, &self.region, self.step, self.max_steps))
+            .filter(|points| points.len() > 1)
+            .map(|points| {
+                let magnitude = points
+                    .iter()
+                    .map(|p| (self.field)(*p).length())
+                    .sum::<f32>()
+                    / points.len() as f32;
+                (points, magnitude)
+            })
+            .collect();
+        let (min, max) = trajectories
+            .iter()
+            .map(|(_, m)| *m)
+            .fold((f32::MAX, 0.0_f32), |(min, max), m| {
+                (min.min(m), max.max(m))
+            });
+
+        let mut lines = Vec::new();
+        for (points, magnitude) in trajectories.iter() {
+            let mut line = crate::line(self.scene)
+                .from(points[0].x, points[0].y)
+                .with_color(gradient(&self.colors, normalize(*magnitude, min, max)))
+                .with_stroke_weight(self.stroke_weight.0);
+            for p in points.iter().skip(1) {
+                line = line.add(*p);
+            }
+            lines.push(line.make());
+        }
+
+        let mut group = crate::empty(self.scene);
+        for line in lines.iter() {
+            group = group.add(*line);
+        }
+        let group = group.with_position(self.region.x(), self.region.y()).make();
+        StreamLinesId { group, lines }
+    }
+}
+
+pub fn stream_lines<'a>(
+    scene: &'a mut Scene,
+    field: impl Fn(Point) -> Vector + 'a,
+    region: Rect,
+    spacing: f32,
+) -> StreamLinesBuilder<'a> {
+    StreamLinesBuilder::new(scene, field, region, spacing)
+}
+
+/// Stream lines of a vector field, each drawn as a line object.
+#[derive(Debug, Clone)]
+pub struct StreamLinesId {
+    group: EmptyId,
+    lines: Vec<LineId>,
+}
+
+impl StreamLinesId {
+    /// [Empty](crate::Empty) object holding the lines as children.
+    pub fn group(&self) -> EmptyId {
+        self.group
+    }
+    pub fn lines(&self) -> &[LineId] {
+        &self.lines
+    }
+    /// Draw every line from its starting point at constant speed, as if
+    /// traced by a particle carried by the field. Combine with
+    /// [AnimBuilder::lag] to release the particles one after the other.
+    pub fn flow(&self) -> Vec<EntityAnimations> {
+        self.lines
+            .iter()
+            .map(|line| EntityAnimations {
+                entity: line.0,
+                animations: vec![
+                    Animation::to(Opacity::FULL)
+                        .with_rate_func(EaseType::Instance)
+                        .into(),
+                    Animation::to(PathCompletion(1.0))
+                        .with_rate_func(EaseType::Linear)
+                        .into(),
+                ],
+            })
+            .collect()
+    }
+    pub fn show_creation(&self) -> Vec<EntityAnimations> {
+        self.lines.iter().map(|l| l.show_creation()).collect()
+    }
+    pub fn fade_in(&self) -> Vec<EntityAnimations> {
+        self.lines.iter().map(|l| l.fade_in()).collect()
+    }
+    pub fn fade_out(&self) -> Vec<EntityAnimations> {
+        self.lines.iter().map(|l| l.fade_out()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_goes_through_colors() {
+        let colors = [Color::BLACK, Color::WHITE, Color::BLACK];
+        assert_eq!(gradient(&colors, 0.0), Color::BLACK);
+        assert_eq!(gradient(&colors, 0.5), Color::WHITE);
+        assert_eq!(gradient(&colors, 1.0), Color::BLACK);
+    }
+
+    #[test]
+    fn trajectories_follow_rotation() {
+        let region = Rect::from_w_h(4.0, 4.0);
+        let rotation = |p: Point| Vector::new(-p.y, p.x);
+        let points = integrate(&rotation, point(1.0, 0.0), &region, 0.05, 200);
+        assert_eq!(points.len(), 201);
+        for p in points.iter() {
+            assert!(((p.to_vector().length()) - 1.0).abs() < 1.0e-3);
+        }
+        // Outward spiral leaves the region before running out of steps.
+        let spiral = |p: Point| Vector::new(p.x - p.y, p.x + p.y);
+        let points = integrate(&spiral, point(1.0, 0.0), &region, 0.05, 200);
+        assert!(points.len() < 201);
+        assert!(points.iter().all(|p| contains(&region, *p)));
+    }
+}
@@ -13,6 +13,10 @@ use crate::Transform;
 
 use crate::{point, Interpolate, Point, Size, EPS_LOW};
 
+pub(crate) mod svg;
+
+pub use svg::SvgPathError;
+
 /// Global path component that can be rendered to screen without any transformation
 #[derive(Debug, Clone, Component)]
 pub struct PixelPath(pub(crate) Path);
@@ -43,6 +47,35 @@ impl Path {
     pub fn builder() -> lyon::path::Builder {
         lyon::path::Builder::new()
     }
+    /// Parse SVG path data, e.g. `"M 0 0 L 1 1 Z"`. The path is closed if
+    /// its last sub-path is closed with `Z`.
+    pub fn from_svg_d(d: &str) -> Result<Self, SvgPathError> {
+        let (raw, closed) = svg::parse(d)?;
+        Ok(Self::new(raw, closed))
+    }
+    /// Write the path as SVG path data using absolute commands. Arcs are
+    /// written as the Bezier curves they were converted into.
+    pub fn to_svg_d(&self) -> String {
+        let mut commands = Vec::new();
+        for event in self.raw.iter() {
+            match event {
+                PathEvent::Begin { at } => commands.push(format!("M {} {}", at.x, at.y)),
+                PathEvent::Line { to, .. } => commands.push(format!("L {} {}", to.x, to.y)),
+                PathEvent::Quadratic { ctrl, to, .. } => {
+                    commands.push(format!("Q {} {} {} {}", ctrl.x, ctrl.y, to.x, to.y))
+                }
+                PathEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => commands.push(format!(
+                    "C {} {} {} {} {} {}",
+                    ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+                )),
+                PathEvent::End { close: true, .. } => commands.push("Z".to_string()),
+                PathEvent::End { .. } => (),
+            }
+        }
+        commands.join(" ")
+    }
     /// Flatten the current path into series of linear line segments
     /// with given tolerance.
     ///
@@ -84,6 +117,108 @@ impl Path {
     pub fn transform(&self, transform: &Transform) -> Self {
         Self::new(self.raw.clone().transformed(&transform.0), self.closed)
     }
+
+    /// Apply `f` to every point of the path, including control points.
+    pub fn map_points(&self, f: impl Fn(Point) -> Point) -> Self {
+        let mut builder = Path::builder();
+        for event in self.raw.iter() {
+            match event {
+                PathEvent::Begin { at } => {
+                    builder.begin(f(at));
+                }
+                PathEvent::Line { to, .. } => {
+                    builder.line_to(f(to));
+                }
+                PathEvent::Quadratic { ctrl, to, .. } => {
+                    builder.quadratic_bezier_to(f(ctrl), f(to));
+                }
+                PathEvent::Cubic {
+                    ctrl1, ctrl2, to, ..
+                } => {
+                    builder.cubic_bezier_to(f(ctrl1), f(ctrl2), f(to));
+                }
+                PathEvent::End { close, .. } => builder.end(close),
+            }
+        }
+        Self::new(builder.build(), self.closed)
+    }
+
+    /// Approximate the path with line segments no longer than `max_length`,
+    /// so that it deforms smoothly under non-affine maps.
+    pub fn resampled(&self, max_length: f32) -> Self {
+        let mut builder = Path::builder();
+        for event in self.flattened(EPS_LOW) {
+            match event {
+                PathEvent::Begin { at } => {
+                    builder.begin(at);
+                }
+                PathEvent::Line { from, to } => {
+                    let n = ((to - from).length() / max_length).ceil().max(1.0) as usize;
+                    for i in 1..=n {
+                        builder.line_to(from.lerp(to, i as f32 / n as f32));
+                    }
+                }
+                PathEvent::End { close, .. } => builder.end(close),
+                _ => (),
+            }
+        }
+        Self::new(builder.build(), self.closed)
+    }
+
+    /// Interpolate point by point between paths made of the same sequence
+    /// of segments, or return `None` if their segments differ.
+    fn interp_pointwise(&self, other: &Self, progress: f32) -> Option<Self> {
+        let mut builder = Path::builder();
+        let mut events = other.raw.iter();
+        for event in self.raw.iter() {
+            match (event, events.next()?) {
+                (PathEvent::Begin { at: a }, PathEvent::Begin { at: b }) => {
+                    builder.begin(a.lerp(b, progress));
+                }
+                (PathEvent::Line { to: a, .. }, PathEvent::Line { to: b, .. }) => {
+                    builder.line_to(a.lerp(b, progress));
+                }
+                (
+                    PathEvent::Quadratic {
+                        ctrl: c1, to: a, ..
+                    },
+                    PathEvent::Quadratic {
+                        ctrl: c2, to: b, ..
+                    },
+                ) => {
+                    builder.quadratic_bezier_to(c1.lerp(c2, progress), a.lerp(b, progress));
+                }
+                (
+                    PathEvent::Cubic {
+                        ctrl1: a1,
+                        ctrl2: a2,
+                        to: a,
+                        ..
+                    },
+                    PathEvent::Cubic {
+                        ctrl1: b1,
+                        ctrl2: b2,
+                        to: b,
+                        ..
+                    },
+                ) => {
+                    builder.cubic_bezier_to(
+                        a1.lerp(b1, progress),
+                        a2.lerp(b2, progress),
+                        a.lerp(b, progress),
+                    );
+                }
+                (PathEvent::End { close: a, .. }, PathEvent::End { close: b, .. }) if a == b => {
+                    builder.end(a);
+                }
+                _ => return None,
+            }
+        }
+        if events.next().is_some() {
+            return None;
+        }
+        Some(Self::new(builder.build(), self.closed))
+    }
 }
 
 impl Interpolate for Path {
@@ -95,6 +230,8 @@ impl Interpolate for Path {
             self.clone()
         } else if progress >= 0.99999 {
             other.clone()
+        } else if let Some(path) = self.interp_pointwise(other, progress) {
+            path
         } else {
             // 1. Calculate the length of initial and final paths (1 and 2)
             // 2. Iterate through each path and construct normalized distance array
@@ -271,8 +408,14 @@ fn normalized_distances(v1: &[f32], v2: &[f32]) -> Vec<f32> {
     combined
 }
 
+/// Trait for shapes whose [Path] is generated from their [Size] and the
+/// shape parameters stored in the component itself.
+///
+/// Shapes implementing this trait have their path rebuilt by
+/// [update_path_from_size](crate::system::update_path_from_size) whenever
+/// their size or the component changes.
 pub trait PathComponent {
-    fn path(size: &Size) -> Path;
+    fn path(&self, size: &Size) -> Path;
 }
 
 pub trait MeasureLength {
@@ -321,7 +464,7 @@ impl GetPartial for Path {
                             builder.line_to(to);
                         }
                     }
-                    PathEvent::End { .. } => {
+                    PathEvent::End { close: true, .. } => {
                         builder.close();
                     }
                     _ => (),
@@ -374,6 +517,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn partial_path_keeps_open_subpaths_open() {
+        let mut builder = Path::svg_builder();
+        builder.move_to(point(0.0, 0.0));
+        builder.line_to(point(1.0, 0.0));
+        builder.line_to(point(1.0, 1.0));
+        builder.move_to(point(3.0, 0.0));
+        builder.line_to(point(4.0, 0.0));
+        builder.line_to(point(4.0, 1.0));
+        builder.line_to(point(3.0, 1.0));
+        builder.close();
+        let path = Path::new(builder.build(), true);
+
+        // Only the closed square is closed, so the partial path has the
+        // length of the open polyline and part of the square.
+        let partial = path.upto(0.6, 0.01);
+        assert!((partial.approximate_length(0.01) - 3.6).abs() < 1.0e-3);
+        let closes = |path: &Path| {
+            path.raw
+                .iter()
+                .filter(|e| matches!(e, PathEvent::End { close: true, .. }))
+                .count()
+        };
+        assert_eq!(closes(&path.upto(0.2, 0.01)), 0);
+        assert_eq!(closes(&path.upto(0.99, 0.01)), 1);
+    }
+
     #[test]
     fn iter_check() {
         let arr = [1, 2, 3, 4, 5];
@@ -540,4 +710,22 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn resample_and_map_points() {
+        let path = Path::from_svg_d("M 0 0 L 1 0 Q 1 1 0 1").unwrap();
+        let resampled = path.resampled(0.1);
+        for e in resampled.raw.iter() {
+            if let PathEvent::Line { from, to } = e {
+                assert!((to - from).length() <= 0.1 + 1.0e-5);
+            }
+        }
+        let mapped = resampled.map_points(|p| point(p.x * 2.0, p.y));
+        assert_eq!(mapped.raw.iter().count(), resampled.raw.iter().count());
+        assert!((mapped.size().width - 2.0).abs() < 1.0e-3);
+
+        // Paths of the same structure are interpolated point by point
+        let half = resampled.interp(&mapped, 0.5);
+        assert!((half.size().width - 1.5).abs() < 1.0e-3);
+    }
 }
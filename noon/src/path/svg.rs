@@ -0,0 +1,277 @@
+//! Parser for the path data (`d` attribute) of SVG `<path>` elements.
+
+use nannou::lyon::geom::{Angle, ArcFlags};
+use nannou::lyon::math::{point, vector};
+use nannou::lyon::path::builder::SvgPathBuilder;
+use nannou::lyon::path::Path as LyonPath;
+
+/// Error for malformed SVG path data, with the byte offset where parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgPathError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, SvgPathError> {
+        Err(SvgPathError {
+            position: self.pos,
+            message: message.to_string(),
+        })
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.data.len()
+            && (self.data[self.pos].is_ascii_whitespace() || self.data[self.pos] == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.data.get(self.pos).copied()
+    }
+
+    fn number(&mut self) -> Result<f32, SvgPathError> {
+        self.skip_separators();
+        let start = self.pos;
+        let at = |pos: usize| self.data.get(pos).copied().unwrap_or(0);
+        let mut end = start;
+        if at(end) == b'-' || at(end) == b'+' {
+            end += 1;
+        }
+        while at(end).is_ascii_digit() {
+            end += 1;
+        }
+        if at(end) == b'.' {
+            end += 1;
+            while at(end).is_ascii_digit() {
+                end += 1;
+            }
+        }
+        if (at(end) == b'e' || at(end) == b'E')
+            && (at(end + 1).is_ascii_digit()
+                || ((at(end + 1) == b'-' || at(end + 1) == b'+') && at(end + 2).is_ascii_digit()))
+        {
+            end += 2;
+            while at(end).is_ascii_digit() {
+                end += 1;
+            }
+        }
+        let text = std::str::from_utf8(&self.data[start..end]).unwrap_or("");
+        match text.parse::<f32>() {
+            Ok(value) => {
+                self.pos = end;
+                Ok(value)
+            }
+            Err(_) => self.error("expected a number"),
+        }
+    }
+
+    /// Arc flags are single digits that may not be separated from what follows.
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        match self.peek() {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => self.error("expected an arc flag"),
+        }
+    }
+}
+
+/// Parse SVG path data into a lyon path. The returned flag indicates whether
+/// the last sub-path was closed with `Z`, as for paths built with
+/// [PathBuilder](crate::PathBuilder).
+pub(crate) fn parse(d: &str) -> Result<(LyonPath, bool), SvgPathError> {
+    let mut parser = Parser {
+        data: d.as_bytes(),
+        pos: 0,
+    };
+    let mut builder = LyonPath::svg_builder();
+    let mut closed = false;
+    let mut command = None;
+
+    while let Some(c) = parser.peek() {
+        let cmd = if c.is_ascii_alphabetic() {
+            parser.pos += 1;
+            c
+        } else {
+            match command {
+                // Coordinates following a move command are implicit line commands.
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(c) if c != b'Z' && c != b'z' => c,
+                _ => return parser.error("expected a command"),
+            }
+        };
+        if command.is_none() && cmd != b'M' && cmd != b'm' {
+            return parser.error("path data must begin with a move command");
+        }
+        command = Some(cmd);
+        // Any command after `Z` starts or extends an open sub-path.
+        closed = matches!(cmd, b'Z' | b'z');
+
+        match cmd {
+            b'M' => {
+                builder.move_to(point(parser.number()?, parser.number()?));
+            }
+            b'm' => builder.relative_move_to(vector(parser.number()?, parser.number()?)),
+            b'L' => {
+                builder.line_to(point(parser.number()?, parser.number()?));
+            }
+            b'l' => builder.relative_line_to(vector(parser.number()?, parser.number()?)),
+            b'H' => builder.horizontal_line_to(parser.number()?),
+            b'h' => builder.relative_horizontal_line_to(parser.number()?),
+            b'V' => builder.vertical_line_to(parser.number()?),
+            b'v' => builder.relative_vertical_line_to(parser.number()?),
+            b'C' => {
+                builder.cubic_bezier_to(
+                    point(parser.number()?, parser.number()?),
+                    point(parser.number()?, parser.number()?),
+                    point(parser.number()?, parser.number()?),
+                );
+            }
+            b'c' => builder.relative_cubic_bezier_to(
+                vector(parser.number()?, parser.number()?),
+                vector(parser.number()?, parser.number()?),
+                vector(parser.number()?, parser.number()?),
+            ),
+            b'S' => builder.smooth_cubic_bezier_to(
+                point(parser.number()?, parser.number()?),
+                point(parser.number()?, parser.number()?),
+            ),
+            b's' => builder.smooth_relative_cubic_bezier_to(
+                vector(parser.number()?, parser.number()?),
+                vector(parser.number()?, parser.number()?),
+            ),
+            b'Q' => {
+                builder.quadratic_bezier_to(
+                    point(parser.number()?, parser.number()?),
+                    point(parser.number()?, parser.number()?),
+                );
+            }
+            b'q' => builder.relative_quadratic_bezier_to(
+                vector(parser.number()?, parser.number()?),
+                vector(parser.number()?, parser.number()?),
+            ),
+            b'T' => builder.smooth_quadratic_bezier_to(point(parser.number()?, parser.number()?)),
+            b't' => builder
+                .smooth_relative_quadratic_bezier_to(vector(parser.number()?, parser.number()?)),
+            b'A' | b'a' => {
+                let radii = vector(parser.number()?, parser.number()?);
+                let x_rotation = Angle::degrees(parser.number()?);
+                let flags = ArcFlags {
+                    large_arc: parser.flag()?,
+                    sweep: parser.flag()?,
+                };
+                let x = parser.number()?;
+                let y = parser.number()?;
+                if cmd == b'A' {
+                    builder.arc_to(radii, x_rotation, flags, point(x, y));
+                } else {
+                    builder.relative_arc_to(radii, x_rotation, flags, vector(x, y));
+                }
+            }
+            b'Z' | b'z' => builder.close(),
+            _ => {
+                parser.pos -= 1;
+                return parser.error("unknown command");
+            }
+        }
+    }
+
+    Ok((builder.build(), closed))
+}
+
+/// Parse a list of numbers separated by whitespace and/or commas, as used in
+/// SVG attributes such as `points` and `transform`. Parsing stops at the first
+/// token that is not a number.
+pub(crate) fn parse_numbers(text: &str) -> Vec<f32> {
+    let mut parser = Parser {
+        data: text.as_bytes(),
+        pos: 0,
+    };
+    let mut numbers = Vec::new();
+    while let Ok(number) = parser.number() {
+        numbers.push(number);
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Path;
+    use nannou::lyon::path::PathEvent;
+
+    fn count(path: &Path, f: impl Fn(&PathEvent) -> bool) -> usize {
+        path.raw.iter().filter(|e| f(e)).count()
+    }
+
+    #[test]
+    fn round_trip() {
+        let d = "M 0 0 L 1 1 Q 2 0 3 1 C 3 2 4 2 4.5 1 Z M 5 5 L 6 5";
+        let path = Path::from_svg_d(d).unwrap();
+        // Only the last sub-path decides whether the path is closed.
+        assert!(!path.closed);
+        assert_eq!(path.to_svg_d(), d);
+        let d = "M 5 5 L 6 5 M 0 0 L 1 1 L 1 0 Z";
+        let path = Path::from_svg_d(d).unwrap();
+        assert!(path.closed);
+        assert_eq!(path.to_svg_d(), d);
+    }
+
+    #[test]
+    fn relative_commands_match_absolute() {
+        let relative = Path::from_svg_d("m1 1 l1 0 h1 v1 c0 1 1 1 1 0 z").unwrap();
+        let absolute = Path::from_svg_d("M1 1 L2 1 H3 V2 C3 3 4 3 4 2 Z").unwrap();
+        assert_eq!(relative.to_svg_d(), absolute.to_svg_d());
+    }
+
+    #[test]
+    fn implicit_commands_and_compact_numbers() {
+        let path = Path::from_svg_d("M0,0 1,1-2-2.5.5.5").unwrap();
+        assert!(!path.closed);
+        assert_eq!(path.to_svg_d(), "M 0 0 L 1 1 L -2 -2.5 L 0.5 0.5");
+        assert_eq!(count(&path, |e| matches!(e, PathEvent::Line { .. })), 3);
+    }
+
+    #[test]
+    fn arcs_become_curves() {
+        let path = Path::from_svg_d("M 0 0 A 1 1 0 0 1 2 0 a1 1 0 1 1 -2 0").unwrap();
+        assert!(
+            count(&path, |e| !matches!(
+                e,
+                PathEvent::Begin { .. } | PathEvent::End { .. } | PathEvent::Line { .. }
+            )) > 0
+        );
+        let size = path.size();
+        assert!((size.width - 2.0).abs() < 1.0e-3);
+        assert!((size.height - 2.0).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn malformed_data_is_rejected() {
+        assert!(Path::from_svg_d("L 1 1").is_err());
+        assert!(Path::from_svg_d("M 0 0 L 1").is_err());
+        assert!(Path::from_svg_d("M 0 0 X 1 1").is_err());
+    }
+}
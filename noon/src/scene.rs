@@ -10,9 +10,19 @@ use crate::Depth;
 use crate::Scale;
 use crate::Transform;
 use crate::{
-    circle, empty, line, rectangle, text, Angle, EmptyBuilder, FontSize, LineBuilder, Opacity,
-    Path, PathCompletion, Position, RectangleBuilder, Size, StrokeColor,
+    annulus, arc, arrow, axes, brace_between, brace_under, circle, code, curve_through, dot,
+    ellipse, empty, function_graph, line, math, matrix, number_line, number_plane,
+    parametric_curve, path, polygon, rectangle, regular_polygon, sector, star, stream_lines,
+    svg_file, svg_str, table, table_of, text, triangle, vector_field, Angle, ArcBuilder, Arrow,
+    ArrowBuilder, AxesBuilder, Brace, BraceBuilder, Circle, CodeBuilder, Dot, DotBuilder, Ellipse,
+    EllipseBuilder, EmptyBuilder, FontError, FontId, FontSize, Fonts, FunctionGraphBuilder,
+    LineBuilder, MathBuilder, MathError, MatrixBuilder, NumberLineBuilder, NumberLineError,
+    NumberPlaneBuilder, Opacity, ParametricCurveBuilder, Path, PathBuilder, PathCompletion, Point,
+    Polygon, PolygonBuilder, Position, Rectangle, RectangleBuilder, SectorBuilder, Size,
+    StreamLinesBuilder, StrokeColor, TableBuilder, Triangle, TriangleBuilder, Vector,
+    VectorFieldBuilder,
 };
+use crate::{InnerRadius, StartAngle, SvgBuilder, SvgError, SweepAngle};
 
 #[derive(Debug, Resource)]
 pub struct Bounds(pub(crate) Rect);
@@ -95,6 +105,7 @@ impl Scene {
         world.insert_resource(Time::default());
         world.insert_resource(bounds);
         world.insert_resource(transform);
+        world.insert_resource(Fonts::new());
 
         let mut updater = Schedule::default();
         updater.add_systems(
@@ -122,9 +133,28 @@ impl Scene {
                     animate_with_relative::<Opacity>,
                     animate_with_relative::<PathCompletion>,
                     animate_with_relative::<FontSize>,
+                    animate_with_relative::<StartAngle>,
+                    animate_with_relative::<SweepAngle>,
+                    animate_with_relative::<InnerRadius>,
+                    animate_text_colors,
                 ),
+                (
+                    update_text_paths,
+                    update_path_from_size::<Circle>,
+                    update_path_from_size::<Rectangle>,
+                    update_path_from_size::<Triangle>,
+                    update_path_from_size::<Dot>,
+                    update_path_from_size::<Arrow>,
+                    update_path_from_size::<Brace>,
+                    update_path_from_size::<Polygon>,
+                    update_path_from_size::<Ellipse>,
+                    update_arc_paths,
+                    update_sector_paths,
+                ),
+                update_brace_paths,
+                forget_morphed_glyphs,
                 (init_from_target::<Path>, print),
-                animate::<Path>,
+                animate_path,
                 update_screen_paths,
             )
                 .chain(),
@@ -156,12 +186,163 @@ impl Scene {
     pub fn line(&mut self) -> LineBuilder {
         line(self)
     }
+    pub fn curve_through(&mut self, points: &[Point]) -> LineBuilder<'_> {
+        curve_through(self, points)
+    }
+    pub fn triangle(&mut self) -> TriangleBuilder<'_> {
+        triangle(self)
+    }
+    pub fn dot(&mut self) -> DotBuilder<'_> {
+        dot(self)
+    }
+    pub fn ellipse(&mut self) -> EllipseBuilder<'_> {
+        ellipse(self)
+    }
+    pub fn arc(&mut self) -> ArcBuilder<'_> {
+        arc(self)
+    }
+    pub fn sector(&mut self) -> SectorBuilder<'_> {
+        sector(self)
+    }
+    pub fn annulus(&mut self, inner: f32, outer: f32) -> SectorBuilder<'_> {
+        annulus(self, inner, outer)
+    }
+    pub fn path(&mut self) -> PathBuilder<'_> {
+        path(self)
+    }
+    /// Load an SVG document from a file, creating one path object per shape.
+    pub fn svg_file(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<SvgBuilder<'_>, SvgError> {
+        svg_file(self, path)
+    }
+    /// Load an SVG document from its source text.
+    pub fn svg_str(&mut self, source: &str) -> Result<SvgBuilder<'_>, SvgError> {
+        svg_str(self, source)
+    }
+    /// Graph of `y = function(x)` for `x` in the given range.
+    pub fn function_graph<'a>(
+        &'a mut self,
+        function: impl Fn(f32) -> f32 + 'a,
+        x_range: std::ops::Range<f32>,
+    ) -> FunctionGraphBuilder<'a> {
+        function_graph(self, function, x_range)
+    }
+    /// Curve traced by `function(t) = (x, y)` for `t` in the given range.
+    pub fn parametric_curve<'a>(
+        &'a mut self,
+        function: impl Fn(f32) -> (f32, f32) + 'a,
+        t_range: std::ops::Range<f32>,
+    ) -> ParametricCurveBuilder<'a> {
+        parametric_curve(self, function, t_range)
+    }
+    pub fn axes(&mut self) -> AxesBuilder<'_> {
+        axes(self)
+    }
+    /// Number line showing the given range of numbers.
+    pub fn number_line(
+        &mut self,
+        range: std::ops::Range<f32>,
+    ) -> Result<NumberLineBuilder<'_>, NumberLineError> {
+        number_line(self, range)
+    }
+    pub fn number_plane(&mut self) -> NumberPlaneBuilder<'_> {
+        number_plane(self)
+    }
+    /// Grid of arrows showing `field` over the region, with the given spacing.
+    pub fn vector_field<'a>(
+        &'a mut self,
+        field: impl Fn(Point) -> Vector + 'a,
+        region: Rect,
+        spacing: f32,
+    ) -> VectorFieldBuilder<'a> {
+        vector_field(self, field, region, spacing)
+    }
+    /// Stream lines of `field` starting from a grid over the region, with
+    /// the given spacing.
+    pub fn stream_lines<'a>(
+        &'a mut self,
+        field: impl Fn(Point) -> Vector + 'a,
+        region: Rect,
+        spacing: f32,
+    ) -> StreamLinesBuilder<'a> {
+        stream_lines(self, field, region, spacing)
+    }
+    /// Brace next to the given object, on the side given by `direction`.
+    pub fn brace_under(
+        &mut self,
+        target: impl Into<Entity>,
+        direction: Direction,
+    ) -> BraceBuilder<'_> {
+        brace_under(self, target, direction)
+    }
+    /// Brace spanning the segment from `from` to `to`.
+    pub fn brace_between(&mut self, from: Point, to: Point) -> BraceBuilder<'_> {
+        brace_between(self, from, to)
+    }
+    /// Table of texts, one for each of the given strings.
+    pub fn table<R: AsRef<[S]>, S: ToString>(&mut self, rows: &[R]) -> TableBuilder<'_> {
+        table(self, rows)
+    }
+    /// Table laying out existing objects.
+    pub fn table_of<R, E>(&mut self, rows: R) -> TableBuilder<'_, E>
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = E>,
+        E: Into<Entity> + From<Entity>,
+    {
+        table_of(self, rows)
+    }
+    /// Matrix of texts between square brackets.
+    pub fn matrix<R: AsRef<[S]>, S: ToString>(&mut self, rows: &[R]) -> MatrixBuilder<'_> {
+        matrix(self, rows)
+    }
+    /// Formula written in a subset of TeX, typeset with the fonts of the
+    /// scene.
+    pub fn math(&mut self, source: &str) -> Result<MathBuilder<'_>, MathError> {
+        math(self, source)
+    }
+    /// Block of source code with line numbers, highlighted as `language`.
+    pub fn code(&mut self, source: &str, language: &str) -> CodeBuilder<'_> {
+        code(self, source, language)
+    }
+    pub fn arrow(&mut self) -> ArrowBuilder<'_> {
+        arrow(self)
+    }
+    pub fn polygon(&mut self, points: &[Point]) -> PolygonBuilder<'_> {
+        polygon(self, points)
+    }
+    pub fn regular_polygon(&mut self, n: usize, radius: f32) -> PolygonBuilder<'_> {
+        regular_polygon(self, n, radius)
+    }
+    pub fn star(&mut self, n: usize, outer: f32, inner: f32) -> PolygonBuilder<'_> {
+        star(self, n, outer, inner)
+    }
     pub fn text(&mut self) -> TextBuilder {
         text(self)
     }
     pub fn group(&mut self) -> EmptyBuilder {
         empty(self)
     }
+    /// Register a font file (e.g. `.ttf` or `.otf`) for use by text objects.
+    pub fn load_font_file(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<FontId, FontError> {
+        self.fonts_mut().load_file(path)
+    }
+    /// Register a font from the raw bytes of a font file for use by text objects.
+    pub fn load_font_bytes(&mut self, bytes: &[u8]) -> Result<FontId, FontError> {
+        self.fonts_mut().load_bytes(bytes)
+    }
+    /// Use the given font for text objects which don't specify their own.
+    pub fn set_default_font(&mut self, font: FontId) {
+        self.fonts_mut().set_default_font(font);
+    }
+    pub fn fonts_mut(&mut self) -> Mut<'_, Fonts> {
+        self.world.get_mut().resource_mut::<Fonts>()
+    }
 
     // pub fn group(&mut self, objects: impl Into<Vec<Entity>>) -> EmptyBuilder {
     //     let objects: Vec<Entity> = objects.into();
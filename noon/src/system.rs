@@ -11,6 +11,10 @@ use crate::{
     Angle, Animation, Animations, Bounds, Circle, FillColor, Interpolate, Path, PathCompletion,
     PixelPath, Position, Size, Transform, Vector, EPS,
 };
+use crate::{Arc, Brace, InnerRadius, Sector, StartAngle, SweepAngle};
+use crate::{BoundingSize, PathComponent};
+use crate::{ColorExtension, FontFamily, FontSize, Fonts, Text, TextLayout};
+use crate::{PathMaps, TextColors, TextFonts, TextGlyphs, TextStyles};
 
 #[derive(Resource)]
 pub struct Time {
@@ -86,12 +90,184 @@ impl Time {
 //     }
 // }
 
+/// [System] for laying out text again whenever its [FontSize] changes, e.g.
+/// during [set_font_size](crate::WithFontSize::set_font_size) animation.
+#[allow(clippy::type_complexity)]
+pub fn update_text_paths(
+    fonts: Res<Fonts>,
+    mut query: Query<
+        (
+            &Text,
+            &FontSize,
+            &TextLayout,
+            &FontFamily,
+            &TextStyles,
+            &mut Path,
+            &mut Size,
+            &mut TextGlyphs,
+        ),
+        Changed<FontSize>,
+    >,
+) {
+    for (text, font_size, layout, family, styles, mut path, mut size, mut glyphs) in
+        query.iter_mut()
+    {
+        let fonts = TextFonts::new(&fonts, family, styles);
+        (*path, *size, *glyphs) = Text::outline(&text.0, font_size.0, layout, &fonts);
+    }
+}
+
+/// [System] for rebuilding the [Path] of shapes implementing [PathComponent]
+/// whenever their [Size] changes, e.g. during [set_size](crate::WithSize::set_size).
+///
+/// For these shapes [Size] is the authoritative attribute, and their
+/// [Path] is always generated from it.
+#[allow(clippy::type_complexity)]
+pub fn update_path_from_size<C: Component + PathComponent>(
+    mut query: Query<(&C, &Size, &mut Path), Or<(Changed<Size>, Changed<C>)>>,
+) {
+    for (shape, size, mut path) in query.iter_mut() {
+        *path = shape.path(size);
+    }
+}
+
+/// [System] for rebuilding the [Path] of arcs when their [Size] or angles change.
+#[allow(clippy::type_complexity)]
+pub fn update_arc_paths(
+    mut query: Query<
+        (&Arc, &Size, &StartAngle, &SweepAngle, &mut Path),
+        Or<(Changed<Size>, Changed<StartAngle>, Changed<SweepAngle>)>,
+    >,
+) {
+    for (arc, size, start, sweep, mut path) in query.iter_mut() {
+        *path = arc.path(size, *start, *sweep);
+    }
+}
+
+/// [System] for rebuilding the [Path] of sectors when their [Size], angles
+/// or inner radius change.
+#[allow(clippy::type_complexity)]
+pub fn update_sector_paths(
+    mut query: Query<
+        (
+            &Sector,
+            &Size,
+            &StartAngle,
+            &SweepAngle,
+            &InnerRadius,
+            &mut Path,
+        ),
+        Or<(
+            Changed<Size>,
+            Changed<StartAngle>,
+            Changed<SweepAngle>,
+            Changed<InnerRadius>,
+        )>,
+    >,
+) {
+    for (sector, size, start, sweep, inner, mut path) in query.iter_mut() {
+        *path = sector.path(size, *start, *sweep, *inner);
+    }
+}
+
+/// [System] for keeping each [Brace] next to its target, and its label at
+/// its tip, following the current attributes of the target.
+///
+/// The brace is only laid out again when the [Size], [Position], [Angle] or
+/// [Scale] of its target changes, and the label only follows the tip when
+/// the brace moves or the label is resized. Animating the brace or its label
+/// directly therefore works as long as the target stays still.
+#[allow(clippy::type_complexity)]
+pub fn update_brace_paths(
+    mut braces: Query<(&Brace, &mut Path, &mut Size, &mut Position, &mut Angle)>,
+    mut others: ParamSet<(
+        Query<
+            (
+                Ref<Size>,
+                Ref<Position>,
+                Option<Ref<Angle>>,
+                Option<Ref<Scale>>,
+            ),
+            Without<Brace>,
+        >,
+        Query<(Ref<Size>, &mut Position), Without<Brace>>,
+    )>,
+) {
+    let mut labels = Vec::new();
+    for (brace, mut path, mut size, mut position, mut angle) in braces.iter_mut() {
+        if let Some(target) = brace.target {
+            if let Ok((t_size, t_position, t_angle, t_scale)) = others.p0().get(target) {
+                // Targets such as groups may lack an angle or scale, as in
+                // the builder.
+                let changed = t_size.is_changed()
+                    || t_position.is_changed()
+                    || t_angle.as_ref().is_some_and(|a| a.is_changed())
+                    || t_scale.as_ref().is_some_and(|s| s.is_changed());
+                if changed {
+                    let t_angle = t_angle.map(|a| *a).unwrap_or_default();
+                    let t_scale = t_scale.map(|s| *s).unwrap_or(Scale::ONE);
+                    let (new_size, new_position, new_angle) =
+                        brace.layout(&t_size, &t_position, &t_angle, &t_scale);
+                    if *size != new_size {
+                        *path = brace.path(&new_size);
+                        *size = new_size;
+                    }
+                    if *position != new_position {
+                        *position = new_position;
+                    }
+                    if *angle != new_angle {
+                        *angle = new_angle;
+                    }
+                }
+            }
+        }
+        if let Some(label) = brace.label {
+            let moved = position.is_changed() || angle.is_changed();
+            labels.push((label, moved, brace.tip(&position, &angle)));
+        }
+    }
+    let mut query = others.p1();
+    for (label, moved, (tip, direction)) in labels {
+        if let Ok((size, mut position)) = query.get_mut(label) {
+            if !moved && !size.is_changed() {
+                continue;
+            }
+            let new_position = crate::object::brace::label_position(tip, direction, &size);
+            if *position != new_position {
+                *position = new_position;
+            }
+        }
+    }
+}
+
+/// [System] for dropping the [TextGlyphs] and [TextColors] of texts once
+/// they start morphing into another object, since their path no longer
+/// shows their characters. Runs before the morph target is resolved.
+pub fn forget_morphed_glyphs(
+    time: Res<Time>,
+    mut query: Query<(&Animations<Path>, &mut TextGlyphs, &mut TextColors)>,
+) {
+    let t = time.seconds;
+    for (animations, mut glyphs, mut colors) in query.iter_mut() {
+        let morphing = animations
+            .0
+            .iter()
+            .any(|animation| animation.start_time < t && animation.has_target().is_some());
+        if morphing && !glyphs.0.is_empty() {
+            *glyphs = TextGlyphs::default();
+            *colors = TextColors::default();
+        }
+    }
+}
+
+/// [System] for computing the screen-space path of each object, as well as
+/// its [BoundingSize] after scaling, rotation and partial completion.
 pub fn update_screen_paths(
     to_pixel: Res<Transform>,
     mut query: Query<
         (
             &mut PixelPath,
-            &mut Size,
+            &mut BoundingSize,
             &Path,
             &PathCompletion,
             &Position,
@@ -101,14 +277,14 @@ pub fn update_screen_paths(
         With<PixelPath>,
     >,
 ) {
-    for (mut global, mut size, local, completion, position, angle, scale) in query.iter_mut() {
+    for (mut global, mut bounding, local, completion, position, angle, scale) in query.iter_mut() {
         let object = Transform::identity()
             .scale(*scale)
             .rotate(*angle)
             .translate(Vector::new(position.x, position.y));
         let pixel = object.transform(*to_pixel);
         let path = local.upto(completion.0, EPS);
-        *size = path.transform(&object).size();
+        *bounding = BoundingSize(path.transform(&object).size());
         *global = PixelPath(path.transform(&pixel));
     }
 }
@@ -265,9 +441,9 @@ pub fn animate_with_multiply<C: Interpolate + Component + Clone + Mul<Output = C
 pub fn animate_position(
     time: Res<Time>,
     bounds: Res<Bounds>,
-    mut query: Query<(&mut Position, &Size, &mut Animations<Position>)>,
+    mut query: Query<(&mut Position, &BoundingSize, &mut Animations<Position>)>,
 ) {
-    for (mut position, size, mut animations) in query.iter_mut() {
+    for (mut position, bounding, mut animations) in query.iter_mut() {
         for animation in animations.0.iter_mut() {
             // let size = path.0.transform().size();
             let t = time.seconds;
@@ -284,14 +460,102 @@ pub fn animate_position(
                     }
                 };
                 // println!("{:?}", size);
-                animation.update_position(&mut position, progress, &bounds, &size);
+                animation.update_position(&mut position, progress, &bounds, &bounding.0);
+            } else if end < t && t <= end + 0.1 {
+                animation.update_position(&mut position, 1.0, &bounds, &bounding.0);
+            }
+        }
+    }
+}
+
+/// [System] for animating the [TextColors] of parts of texts, starting
+/// from the [FillColor] of the text for characters without a color.
+pub fn animate_text_colors(
+    time: Res<Time>,
+    mut query: Query<(&mut TextColors, &FillColor, &mut Animations<TextColors>)>,
+) {
+    for (mut colors, fill, mut animations) in query.iter_mut() {
+        for animation in animations.0.iter_mut() {
+            let t = time.seconds;
+            let begin = animation.start_time;
+            let duration = animation.duration;
+            let end = animation.start_time + animation.duration + 0.0;
+
+            if begin < t && t <= end {
+                let progress = {
+                    if duration > 0.0 {
+                        animation.rate_func.calculate((t - begin) / duration)
+                    } else {
+                        1.0
+                    }
+                };
+                animation.update_text_colors(&mut colors, progress, fill.0);
             } else if end < t && t <= end + 0.1 {
-                animation.update_position(&mut position, 1.0, &bounds, &size);
+                animation.update_text_colors(&mut colors, 1.0, fill.0);
             }
         }
     }
 }
 
+/// [System] for animating [Path]s, including pointwise deformations given
+/// in scene coordinates, which are turned into regular animations of the
+/// [Path] when they start.
+#[allow(clippy::type_complexity)]
+pub fn animate_path(
+    time: Res<Time>,
+    mut query: Query<(
+        &mut Path,
+        &Position,
+        &Angle,
+        &Scale,
+        &mut Animations<Path>,
+        Option<&mut PathMaps>,
+    )>,
+) {
+    let t = time.seconds;
+    for (mut path, position, angle, scale, mut animations, maps) in query.iter_mut() {
+        for animation in animations.0.iter_mut() {
+            update_path_at(animation, &mut path, t);
+        }
+
+        // Maps start from the path as updated above, e.g. once a previous
+        // deformation has completed, and are played from the same frame.
+        if let Some(mut maps) = maps {
+            let to_scene = Transform::identity()
+                .scale(*scale)
+                .rotate(*angle)
+                .translate(Vector::new(position.x, position.y));
+            maps.0.retain(|map| {
+                let started = map.start_time < t;
+                if started {
+                    let mut animation = map.resolve(&path, &to_scene);
+                    update_path_at(&mut animation, &mut path, t);
+                    animations.0.push(animation);
+                }
+                !started
+            });
+        }
+    }
+}
+
+fn update_path_at(animation: &mut Animation<Path>, path: &mut Path, t: f32) {
+    let begin = animation.start_time;
+    let duration = animation.duration;
+    let end = animation.start_time + animation.duration;
+
+    if begin < t && t <= end {
+        let progress = if duration > 0.0 {
+            animation.rate_func.calculate((t - begin) / duration)
+        } else {
+            1.0
+        };
+        animation.update(path, progress);
+    } else if end < t && t <= end + 0.1 {
+        animation.update(path, 1.0);
+    }
+}
+
+#[allow(clippy::type_complexity)]
 pub fn draw(
     draw: NonSend<nannou::Draw>,
     query: Query<(
@@ -301,45 +565,61 @@ pub fn draw(
         &Opacity,
         &PixelPath,
         &Depth,
-        &Size,
+        &BoundingSize,
         &HasFill,
+        Option<(&TextGlyphs, &TextColors, &Path)>,
     )>,
 ) {
-    for (stroke_color, stroke_weight, fill_color, alpha, path, depth, size, has_fill) in
+    for (stroke_color, stroke_weight, fill_color, alpha, path, depth, size, has_fill, text) in
         query.iter()
     {
         if alpha.is_visible() {
-            if has_fill.0 {
-                let fill = Rgba {
-                    color: fill_color.0,
+            let draw_path = |raw: &nannou::lyon::path::Path, fill_color, stroke_color| {
+                if has_fill.0 {
+                    let fill = Rgba {
+                        color: fill_color,
+                        alpha: alpha.0,
+                    };
+                    // Draw fill first
+                    draw.path().fill().z(depth.0).color(fill).events(raw);
+                }
+
+                let stroke = Rgba {
+                    color: stroke_color,
                     alpha: alpha.0,
                 };
-                // Draw fill first
-                draw.path()
-                    .fill()
-                    .z(depth.0)
-                    .color(fill)
-                    .events(&path.0.raw);
-            }
-
-            let stroke = Rgba {
-                color: stroke_color.0,
-                alpha: alpha.0,
+                // Draw stroke on top
+                if !stroke_weight.is_none() {
+                    let thickness = if stroke_weight.is_auto() {
+                        size.0.width.min(size.0.height) / 5.0
+                    } else {
+                        stroke_weight.0
+                    };
+                    draw.path()
+                        .stroke()
+                        .z(depth.0)
+                        .join_round()
+                        .color(stroke)
+                        .stroke_weight(thickness)
+                        .events(raw);
+                }
             };
-            // Draw stroke on top
-            if !stroke_weight.is_none() {
-                let thickness = if stroke_weight.is_auto() {
-                    size.width.min(size.height) / 5.0
-                } else {
-                    stroke_weight.0
-                };
-                draw.path()
-                    .stroke()
-                    .z(depth.0)
-                    .join_round()
-                    .color(stroke)
-                    .stroke_weight(thickness)
-                    .events(&path.0.raw);
+
+            match text {
+                // Parts of texts with colors of their own, as long as the
+                // path has the contours of the glyphs. The drawn path may be
+                // partial, but its contours keep their order.
+                Some((glyphs, colors, local))
+                    if colors.0.iter().any(Option::is_some) && glyphs.describes(&local.raw) =>
+                {
+                    for (color, raw) in glyphs.split_by_color(&path.0.raw, colors) {
+                        match color {
+                            Some(color) => draw_path(&raw, color, color.brighten()),
+                            None => draw_path(&raw, fill_color.0, stroke_color.0),
+                        }
+                    }
+                }
+                _ => draw_path(&path.0.raw, fill_color.0, stroke_color.0),
             }
         }
     }